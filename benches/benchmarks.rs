@@ -1,46 +1,43 @@
+use aoc_25::puzzle::{Puzzle, Visitor};
+use aoc_25::utils::{resolve_input, InputConfig};
 use criterion::{criterion_group, criterion_main, Criterion};
-use std::fs::read_to_string;
 use std::hint::black_box;
 
-macro_rules! benchmark {
-    ($year:tt $($day:tt),*) => {
-        fn $year(c: &mut Criterion) {
-            use aoc_25::$year;
+/// Registers a benchmark group for every puzzle with an input file
+struct Benchmark<'a>(&'a mut Criterion);
 
-            $(
-                {
-                    let day_num = stringify!($day).trim_start_matches("day").parse::<u32>().unwrap();
-                    let path = format!("inputs/{}/day{:02}.txt", stringify!($year), day_num);
+impl Visitor for Benchmark<'_> {
+    fn visit<P: Puzzle>(&mut self) {
+        let source = resolve_input(P::YEAR, P::DAY, &InputConfig::from_env());
 
-                    if let Ok(data) = read_to_string(&path) {
-                        let parsed = $year::$day::parse(&data);
+        if let Ok(data) = source.read() {
+            let parsed = match P::parse(&data) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("Skipping {source}: {e}");
+                    return;
+                }
+            };
 
-                        // Benchmark parse, part1, and part2
-                        let mut group = c.benchmark_group(format!("{}_day{:02}", stringify!($year), day_num));
-                        group.sample_size(40);
+            // Benchmark parse, part1, and part2
+            let mut group = self
+                .0
+                .benchmark_group(format!("year{}_day{:02}", P::YEAR, P::DAY));
+            group.sample_size(40);
 
-                        group.bench_function("parse", |b| {
-                            b.iter(|| $year::$day::parse(black_box(&data)))
-                        });
+            group.bench_function("parse", |b| b.iter(|| P::parse(black_box(&data))));
 
-                        group.bench_function("part1", |b| {
-                            b.iter(|| $year::$day::part1(black_box(&parsed)))
-                        });
+            group.bench_function("part1", |b| b.iter(|| P::part1(black_box(&parsed))));
 
-                        group.bench_function("part2", |b| {
-                            b.iter(|| $year::$day::part2(black_box(&parsed)))
-                        });
-                        group.finish();
-                    }
-                }
-            )*
+            group.bench_function("part2", |b| b.iter(|| P::part2(black_box(&parsed))));
+            group.finish();
         }
     }
 }
 
-benchmark!(year2025
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11
-);
+fn puzzles(c: &mut Criterion) {
+    aoc_25::visit_puzzles(&mut Benchmark(c));
+}
 
 fn custom_criterion() -> Criterion {
     Criterion::default()
@@ -51,6 +48,6 @@ fn custom_criterion() -> Criterion {
 criterion_group! {
     name = benches;
     config = custom_criterion();
-    targets = year2025
+    targets = puzzles
 }
 criterion_main!(benches);
@@ -1,49 +1,70 @@
-use std::fs::read_to_string;
+use aoc_25::puzzle::{Puzzle, Visitor};
+use aoc_25::utils::{resolve_input, InputConfig};
 use std::hint::black_box;
 use std::time::{Duration, Instant};
 
-macro_rules! benchmark_year {
-    ($year:tt $($day:tt),*) => {{
-        use aoc_25::$year;
-
-        let year_num = stringify!($year).trim_start_matches("year").parse::<u32>().unwrap();
-        println!("{}:", year_num);
+/// Times a single run of each part, totalled per year
+#[derive(Default)]
+struct Summary {
+    year: Option<u32>,
+    total: Duration,
+}
 
-        let mut total = Duration::ZERO;
+impl Summary {
+    fn finish_year(&mut self) {
+        if let Some(year) = self.year.take() {
+            println!(
+                "\nTotal {}: {:.2} μs\n",
+                year,
+                self.total.as_secs_f64() * 1_000_000.0
+            );
+        }
+        self.total = Duration::ZERO;
+    }
+}
 
-        $(
-            {
-                let day_num = stringify!($day).trim_start_matches("day").parse::<u32>().unwrap();
-                let path = format!("inputs/{}/day{:02}.txt", stringify!($year), day_num);
+impl Visitor for Summary {
+    fn visit<P: Puzzle>(&mut self) {
+        if self.year != Some(P::YEAR) {
+            self.finish_year();
+            self.year = Some(P::YEAR);
+            println!("{}:", P::YEAR);
+        }
 
-                if let Ok(data) = read_to_string(&path) {
-                    let parsed = $year::$day::parse(&data);
+        let source = resolve_input(P::YEAR, P::DAY, &InputConfig::from_env());
 
-                    // Single run benchmark for timing
-                    let start = Instant::now();
-                    black_box($year::$day::part1(black_box(&parsed)));
-                    let part1_time = start.elapsed();
+        if let Ok(data) = source.read() {
+            let parsed = match P::parse(&data) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("Skipping {source}: {e}");
+                    return;
+                }
+            };
 
-                    let start = Instant::now();
-                    black_box($year::$day::part2(black_box(&parsed)));
-                    let part2_time = start.elapsed();
+            // Single run benchmark for timing
+            let start = Instant::now();
+            black_box(P::part1(black_box(&parsed)));
+            let part1_time = start.elapsed();
 
-                    let day_total = part1_time + part2_time;
-                    total += day_total;
+            let start = Instant::now();
+            black_box(P::part2(black_box(&parsed)));
+            let part2_time = start.elapsed();
 
-                    println!("  Day {:02}: Part 1: {:>8.2} μs, Part 2: {:>8.2} μs",
-                        day_num,
-                        part1_time.as_secs_f64() * 1_000_000.0,
-                        part2_time.as_secs_f64() * 1_000_000.0
-                    );
-                }
-            }
-        )*
+            self.total += part1_time + part2_time;
 
-        println!("\nTotal {}: {:.2} μs\n", year_num, total.as_secs_f64() * 1_000_000.0);
-    }}
+            println!(
+                "  Day {:02}: Part 1: {:>8.2} μs, Part 2: {:>8.2} μs",
+                P::DAY,
+                part1_time.as_secs_f64() * 1_000_000.0,
+                part2_time.as_secs_f64() * 1_000_000.0
+            );
+        }
+    }
 }
 
 fn main() {
-    benchmark_year!(year2025 day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11);
+    let mut summary = Summary::default();
+    aoc_25::visit_puzzles(&mut summary);
+    summary.finish_year();
 }
@@ -0,0 +1,261 @@
+//! Ledger of recorded answers, stored as `answers/yearXXXX.toml`.
+//!
+//! Only the small TOML subset the ledger writes is understood: one `[dayXX]`
+//! table per day holding `part1`/`part2` string (or bare) values.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::utils::InputConfig;
+
+/// Outcome of comparing a computed answer against the ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail { expected } => write!(f, "FAIL, expected {expected}"),
+            Status::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    days: BTreeMap<u32, [Option<String>; 2]>,
+}
+
+impl Ledger {
+    /// Location of the ledger for a year (example answers live in a separate file)
+    pub fn path_for(year: u32, use_test: bool) -> PathBuf {
+        let suffix = if use_test { "_test" } else { "" };
+        PathBuf::from(format!("answers/year{year}{suffix}.toml"))
+    }
+
+    /// Location of the ledger matching where inputs are read from.
+    ///
+    /// Inputs under an `--input-dir` have their own ledger in `answers/` inside
+    /// that directory. An explicit input file has no ledger at all.
+    pub fn path_for_input(year: u32, config: &InputConfig) -> Option<PathBuf> {
+        match (&config.input, config.use_test, &config.input_dir) {
+            (Some(_), ..) => None,
+            (None, true, _) => Some(Self::path_for(year, true)),
+            (None, false, Some(dir)) => Some(dir.join(Self::path_for(year, false))),
+            (None, false, None) => Some(Self::path_for(year, false)),
+        }
+    }
+
+    /// Load a ledger, treating a missing file as empty
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map(|days| Self { path, days }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self {
+                path,
+                days: BTreeMap::new(),
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the ledger back to the file it was loaded from
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, self.to_toml())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Recorded answer for a day and part (1 or 2)
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.days.get(&day)?.get(part_index(part)?)?.as_deref()
+    }
+
+    /// Record an answer for a day and part (1 or 2)
+    pub fn set(&mut self, day: u32, part: u32, answer: &str) {
+        if let Some(index) = part_index(part) {
+            self.days.entry(day).or_default()[index] = Some(answer.to_string());
+        }
+    }
+
+    /// Compare a computed answer against the recorded one
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Status {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.to_string(),
+            },
+            None => Status::Unknown,
+        }
+    }
+
+    fn parse(text: &str) -> io::Result<BTreeMap<u32, [Option<String>; 2]>> {
+        let invalid = |line: usize, message: &str| {
+            io::Error::new(ErrorKind::InvalidData, format!("line {line}: {message}"))
+        };
+
+        let mut days: BTreeMap<u32, [Option<String>; 2]> = BTreeMap::new();
+        let mut current = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let day = table
+                    .trim()
+                    .strip_prefix("day")
+                    .and_then(|d| d.parse::<u32>().ok())
+                    .ok_or_else(|| invalid(line_no, "expected a [dayXX] table"))?;
+                days.entry(day).or_default();
+                current = Some(day);
+                continue;
+            }
+
+            let day = current.ok_or_else(|| invalid(line_no, "key outside of a [dayXX] table"))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid(line_no, "expected `key = value`"))?;
+            let index = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse::<u32>().ok())
+                .and_then(part_index)
+                .ok_or_else(|| invalid(line_no, "expected part1 or part2"))?;
+            let value =
+                parse_value(value.trim()).ok_or_else(|| invalid(line_no, "malformed value"))?;
+
+            days.entry(day).or_default()[index] = Some(value);
+        }
+
+        Ok(days)
+    }
+
+    fn to_toml(&self) -> String {
+        let mut out = String::new();
+        for (day, parts) in &self.days {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[day{day:02}]\n"));
+            for (index, answer) in parts.iter().enumerate() {
+                if let Some(answer) = answer {
+                    out.push_str(&format!("part{} = {}\n", index + 1, quote(answer)));
+                }
+            }
+        }
+        out
+    }
+}
+
+fn part_index(part: u32) -> Option<usize> {
+    match part {
+        1 => Some(0),
+        2 => Some(1),
+        _ => None,
+    }
+}
+
+fn quote(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    let Some(inner) = value.strip_prefix('"') else {
+        // Bare values (e.g. integers) are taken verbatim
+        return (!value.is_empty()).then(|| value.to_string());
+    };
+
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return chars.as_str().trim().is_empty().then_some(result),
+            '\\' => result.push(chars.next()?),
+            _ => result.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.set(1, 1, "3");
+        ledger.set(1, 2, "say \"hi\"");
+        ledger.set(12, 1, "42");
+
+        let days = Ledger::parse(&ledger.to_toml()).unwrap();
+        assert_eq!(days, ledger.days);
+    }
+
+    #[test]
+    fn test_check() {
+        let ledger = Ledger {
+            path: PathBuf::new(),
+            days: Ledger::parse("[day01]\npart1 = 3\n").unwrap(),
+        };
+        assert_eq!(ledger.check(1, 1, "3"), Status::Pass);
+        assert_eq!(
+            ledger.check(1, 1, "4"),
+            Status::Fail {
+                expected: "3".to_string()
+            }
+        );
+        assert_eq!(ledger.check(1, 2, "6"), Status::Unknown);
+    }
+
+    #[test]
+    fn test_path_for_input() {
+        let mut config = InputConfig::default();
+        let path = |p: &str| Some(PathBuf::from(p));
+        assert_eq!(
+            Ledger::path_for_input(2025, &config),
+            path("answers/year2025.toml")
+        );
+
+        config.input_dir = Some(PathBuf::from("/data/alice"));
+        assert_eq!(
+            Ledger::path_for_input(2025, &config),
+            path("/data/alice/answers/year2025.toml")
+        );
+
+        // Examples don't depend on the input directory
+        config.use_test = true;
+        assert_eq!(
+            Ledger::path_for_input(2025, &config),
+            path("answers/year2025_test.toml")
+        );
+
+        config.input = Some("mine.txt".to_string());
+        assert_eq!(Ledger::path_for_input(2025, &config), None);
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(Ledger::parse("part1 = 3").is_err());
+        assert!(Ledger::parse("[day01]\npart3 = 3").is_err());
+        assert!(Ledger::parse("[day01]\npart1 = \"3").is_err());
+    }
+}
@@ -1,2 +1,12 @@
+pub mod answers;
+pub mod puzzle;
+pub mod runner;
 pub mod utils;
 pub mod year2025;
+
+use puzzle::Visitor;
+
+/// Visit every registered puzzle, ordered by year then day
+pub fn visit_puzzles<V: Visitor>(visitor: &mut V) {
+    year2025::visit(visitor);
+}
@@ -1,98 +1,620 @@
+mod scaffold;
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
 use std::env::args;
-use std::fs::read_to_string;
+use std::fmt;
+use std::io::{self, ErrorKind};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
 use std::time::{Duration, Instant};
 
-mod utils;
-mod year2025;
+use aoc_25::answers::{Ledger, Status};
+use aoc_25::runner::{self, Answers, Solution, Timings};
+use aoc_25::utils::{resolve_input, InputConfig, InputSource, ParseError};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+/// Options that consume the argument following them
+const VALUE_OPTIONS: &[&str] = &["--format", "--timeout", "--jobs", "--input", "--input-dir"];
+
+/// Worker threads get a generous stack as some solutions recurse deeply
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+thread_local! {
+    /// Whether this thread runs a solution for [`isolate`], which reports its panics itself
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    /// Message of the last panic on a capturing thread, recorded by the panic hook
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// What to do with computed answers besides printing them
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Print,
+    Check,
+    Record,
+}
+
+/// How results are written to stdout
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Why a solution produced no answers
+enum Failure {
+    MissingInput,
+    Read(io::Error),
+    Parse(ParseError),
+    Panic(String),
+    Timeout(Duration),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::MissingInput => write!(f, "Missing input"),
+            Failure::Read(e) => write!(f, "Failed to read input: {e}"),
+            Failure::Parse(e) => write!(f, "Parse error at {e}"),
+            Failure::Panic(message) => write!(f, "Panicked: {message}"),
+            Failure::Timeout(limit) => write!(f, "Timed out after {limit:?}"),
+        }
+    }
+}
+
+/// Command line options shared by every solution run
+struct Options {
+    input: InputConfig,
+    mode: Mode,
+    timeout: Option<Duration>,
+}
 
-struct Solution {
+/// Everything known about one solution after running it
+struct Report {
     year: u32,
     day: u32,
-    wrapper: fn(&str) -> (String, String),
+    source: InputSource,
+    result: Result<Answers, Failure>,
+    statuses: Option<[Status; 2]>,
+}
+
+#[derive(Default)]
+struct Totals {
+    stars: u32,
+    timings: Timings,
+    failures: u32,
+    errors: u32,
+    wall: Duration,
 }
 
-fn main() {
+fn main() -> ExitCode {
+    // `new <year> <day>` scaffolds a day instead of running solutions
+    if args().nth(1).as_deref() == Some("new") {
+        return new_day();
+    }
+
     // Check for --test flag
     let use_test = args().any(|arg| arg == "--test");
 
+    // Check for --check / --record flags
+    let mode = match (
+        args().any(|arg| arg == "--check"),
+        args().any(|arg| arg == "--record"),
+    ) {
+        (true, true) => {
+            eprintln!("--check and --record can't be combined");
+            return ExitCode::FAILURE;
+        }
+        (true, false) => Mode::Check,
+        (false, true) => Mode::Record,
+        (false, false) => Mode::Print,
+    };
+
+    // Check for --format <text|json|csv>
+    let format = match option_value("--format").as_deref() {
+        None | Some("text") => Format::Text,
+        Some("json") => Format::Json,
+        Some("csv") => Format::Csv,
+        Some(other) => {
+            eprintln!("Unknown format {other:?}, expected text, json or csv");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Check for --timeout <seconds>
+    let timeout = match option_value("--timeout").map(|value| value.parse::<f64>()) {
+        None => None,
+        Some(Ok(secs)) if secs > 0.0 && secs.is_finite() => Some(Duration::from_secs_f64(secs)),
+        Some(_) => {
+            eprintln!("--timeout expects a positive number of seconds");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Check for --jobs <n>, where 0 means one per core
+    let jobs = match option_value("--jobs").map(|value| value.parse::<usize>()) {
+        None => 1,
+        Some(Ok(jobs)) => jobs,
+        Some(Err(_)) => {
+            eprintln!("--jobs expects a number of parallel jobs");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Check for --input <path|-> and --input-dir <dir> (or AOC_INPUT_DIR)
+    let mut input = InputConfig::from_env();
+    input.use_test = use_test;
+    input.input = option_value("--input");
+    if let Some(dir) = option_value("--input-dir") {
+        input.input_dir = Some(dir.into());
+    }
+
+    // Ledgers follow the input directory, but a one-off input file has none
+    if mode != Mode::Print && input.input.is_some() {
+        eprintln!("--check and --record can't be combined with --input");
+        return ExitCode::FAILURE;
+    }
+
+    let options = Options {
+        input,
+        mode,
+        timeout,
+    };
+
     // Parse command line options
-    let mut iter = args().skip(1).flat_map(|arg| arg.parse::<u32>().ok());
+    let mut iter = positional_args().flat_map(|arg| arg.parse::<u32>().ok());
     let (year, day) = (iter.next(), iter.next());
 
-    // Build list of all solutions
-    let solutions = [year2025()];
+    capture_panics();
 
-    // Run selected solutions
-    let (stars, duration) = solutions
-        .iter()
-        .flatten()
+    // Answer ledgers, loaded lazily per year
+    let mut ledgers = BTreeMap::new();
+
+    if format == Format::Csv {
+        println!(
+            "year,day,part1,part2,parse_ns,part1_ns,part2_ns,part1_status,part2_status,input,error"
+        );
+    }
+
+    // Select solutions
+    let selected: Vec<Solution> = runner::puzzles()
         .filter(|s| year.is_none() || year == Some(s.year))
         .filter(|s| day.is_none() || day == Some(s.day))
-        .fold((0, Duration::ZERO), |acc, s| run_solution(acc, s, use_test));
+        .collect();
 
-    // Print totals
-    if args().any(|arg| arg == "--totals") {
-        println!("⭐ {stars}");
-        println!("🕒 {duration:?}");
+    // An explicit input only makes sense for a single day
+    if options.input.input.is_some() && selected.len() != 1 {
+        eprintln!("--input requires selecting exactly one solution, e.g. `2025 1`");
+        return ExitCode::FAILURE;
     }
-}
 
-fn run_solution(
-    (stars, duration): (u32, Duration),
-    solution: &Solution,
-    use_test: bool,
-) -> (u32, Duration) {
-    let Solution { year, day, wrapper } = solution;
-    let path = if use_test {
-        format!("tests/year{year}/day{day:02}_input.txt")
+    // Recording needs every ledger it will write to, so don't run anything if one is unreadable
+    if mode == Mode::Record {
+        for solution in &selected {
+            if ledgers.contains_key(&solution.year) {
+                continue;
+            }
+            match load_ledger(solution.year, &options.input) {
+                Some(ledger) => ledgers.insert(solution.year, Some(ledger)),
+                None => return ExitCode::FAILURE,
+            };
+        }
+    }
+
+    // Run selected solutions, in parallel if requested, but always report in order
+    let instant = Instant::now();
+    let reports: Box<dyn Iterator<Item = Report>> = if jobs == 1 {
+        Box::new(selected.into_iter().map(|s| run_solution(&s, &options)))
     } else {
-        format!("inputs/year{year}/day{day:02}.txt")
+        let pool = match ThreadPoolBuilder::new().num_threads(jobs).build() {
+            Ok(pool) => pool,
+            Err(e) => {
+                eprintln!("Failed to start {jobs} jobs: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+        let reports: Vec<Report> = pool.install(|| {
+            selected
+                .par_iter()
+                .map(|s| run_solution(s, &options))
+                .collect()
+        });
+        Box::new(reports.into_iter())
     };
 
-    if let Ok(data) = read_to_string(&path) {
-        let instant = Instant::now();
-        let (part1, part2) = wrapper(&data);
-        let elapsed = instant.elapsed();
+    let mut totals = reports.fold(Totals::default(), |mut totals, mut report| {
+        settle(&mut report, &options, &mut ledgers);
+
+        match format {
+            Format::Text => print_text(&report, use_test),
+            Format::Json => print_json(&report),
+            Format::Csv => print_csv(&report),
+        }
+
+        match &report.result {
+            Ok(output) => {
+                totals.stars += 2;
+                totals.timings += output.timings;
+            }
+            Err(Failure::MissingInput) => {}
+            Err(_) => totals.errors += 1,
+        }
+        totals.failures += report
+            .statuses
+            .iter()
+            .flatten()
+            .filter(|status| matches!(status, Status::Fail { .. }))
+            .count() as u32;
+        totals
+    });
+    totals.wall = instant.elapsed();
 
+    // Persist newly recorded answers
+    if mode == Mode::Record {
+        for ledger in ledgers.values().flatten() {
+            match ledger.save() {
+                Ok(()) => eprintln!("Recorded answers in {}", ledger.path().display()),
+                Err(e) => {
+                    eprintln!("Failed to write {}: {e}", ledger.path().display());
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    // Print totals
+    if format == Format::Text && args().any(|arg| arg == "--totals") {
+        println!("⭐ {}", totals.stars);
         println!(
-            "{year} Day {day:02}{}",
-            if use_test { " (TEST)" } else { "" }
+            "🕒 {:?} summed (parse {:?}, part 1 {:?}, part 2 {:?})",
+            totals.timings.total(),
+            totals.timings.parse,
+            totals.timings.part1,
+            totals.timings.part2
         );
-        println!("    Part 1: {part1}");
-        println!("    Part 2: {part2}");
+        println!("⏱️ {:?} wall clock", totals.wall);
+    }
+
+    if totals.errors > 0 {
+        eprintln!("{} solution(s) failed to run", totals.errors);
+    }
+    if totals.failures > 0 {
+        eprintln!("{} answer(s) did not match the ledger", totals.failures);
+    }
 
-        (stars + 2, duration + elapsed)
+    if totals.errors > 0 || totals.failures > 0 {
+        ExitCode::FAILURE
     } else {
-        eprintln!("{year} Day {day:02}");
-        eprintln!("    Missing input!");
-        eprintln!("    Place input file in {path}");
+        ExitCode::SUCCESS
+    }
+}
+
+fn new_day() -> ExitCode {
+    let mut iter = positional_args().skip(1).map(|arg| arg.parse::<u32>());
+    let (Some(Ok(year)), Some(Ok(day))) = (iter.next(), iter.next()) else {
+        eprintln!("Usage: new <year> <day>");
+        return ExitCode::FAILURE;
+    };
+
+    match scaffold::new_day(Path::new("."), year, day) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to scaffold {year} day {day:02}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Value following a command line option, e.g. `--format json`
+fn option_value(name: &str) -> Option<String> {
+    args().skip_while(|arg| arg != name).nth(1)
+}
+
+/// Arguments that are neither options nor option values
+fn positional_args() -> impl Iterator<Item = String> {
+    args()
+        .skip(1)
+        .scan(false, |takes_value, arg| {
+            let is_value = std::mem::replace(takes_value, VALUE_OPTIONS.contains(&arg.as_str()));
+            Some((!is_value).then_some(arg))
+        })
+        .flatten()
+}
+
+/// Run a single solution, safe to call from several threads at once
+fn run_solution(solution: &Solution, options: &Options) -> Report {
+    let Solution { year, day, .. } = *solution;
+    let source = resolve_input(year, day, &options.input);
+
+    let result = match source.read() {
+        Ok(data) => isolate(*solution, data, options.timeout),
+        Err(e) if e.kind() == ErrorKind::NotFound => Err(Failure::MissingInput),
+        Err(e) => Err(Failure::Read(e)),
+    };
+
+    Report {
+        year,
+        day,
+        source,
+        result,
+        statuses: None,
+    }
+}
+
+/// Check a report's answers against, or record them into, its year's ledger
+fn settle(report: &mut Report, options: &Options, ledgers: &mut BTreeMap<u32, Option<Ledger>>) {
+    let Report {
+        year, day, result, ..
+    } = report;
+
+    if let (Mode::Check | Mode::Record, Ok(output)) = (options.mode, result) {
+        let ledger = ledgers
+            .entry(*year)
+            .or_insert_with(|| load_ledger(*year, &options.input));
+        report.statuses = apply_ledger(options.mode, ledger.as_mut(), *day, output);
+    }
+}
+
+/// Run a solution on a worker thread, turning panics and overruns into failures.
+///
+/// A solution that overruns its budget can't be stopped, so its thread is left
+/// to finish (or not) in the background while the remaining days run.
+fn isolate(
+    solution: Solution,
+    data: String,
+    timeout: Option<Duration>,
+) -> Result<Answers, Failure> {
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::Builder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            CAPTURING.set(true);
+            let result = panic::catch_unwind(AssertUnwindSafe(|| solution.solve(&data)));
+            let result = match result {
+                Ok(result) => result.map_err(Failure::Parse),
+                Err(_) => Err(Failure::Panic(
+                    PANIC_MESSAGE
+                        .take()
+                        .unwrap_or_else(|| "unknown panic".to_string()),
+                )),
+            };
+            // The receiver is gone if we already timed out
+            let _ = sender.send(result);
+        });
+
+    if let Err(e) = worker {
+        return Err(Failure::Panic(format!("failed to spawn worker: {e}")));
+    }
+
+    match timeout {
+        Some(limit) => match receiver.recv_timeout(limit) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(Failure::Timeout(limit)),
+            Err(RecvTimeoutError::Disconnected) => Err(Failure::Panic("worker vanished".into())),
+        },
+        None => receiver
+            .recv()
+            .unwrap_or_else(|_| Err(Failure::Panic("worker vanished".into()))),
+    }
+}
+
+/// Record panic messages for [`isolate`] instead of printing them, leaving
+/// panics on any other thread to the default hook
+fn capture_panics() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !CAPTURING.get() {
+            return default(info);
+        }
+        let payload = info.payload_as_str().unwrap_or("non-string panic payload");
+        let message = match info.location() {
+            Some(location) => format!("{payload} at {location}"),
+            None => payload.to_string(),
+        };
+        PANIC_MESSAGE.set(Some(message));
+    }));
+}
 
-        (stars, duration)
+/// Check answers against, or record them into, a year's ledger
+fn apply_ledger(
+    mode: Mode,
+    ledger: Option<&mut Ledger>,
+    day: u32,
+    output: &Answers,
+) -> Option<[Status; 2]> {
+    match (mode, ledger) {
+        (Mode::Check, Some(ledger)) => Some([
+            ledger.check(day, 1, &output.part1),
+            ledger.check(day, 2, &output.part2),
+        ]),
+        // An unreadable ledger can't vouch for anything
+        (Mode::Check, None) => Some([(); 2].map(|_| Status::Fail {
+            expected: "a readable ledger".to_string(),
+        })),
+        (Mode::Record, Some(ledger)) => {
+            ledger.set(day, 1, &output.part1);
+            ledger.set(day, 2, &output.part2);
+            None
+        }
+        (Mode::Record, None) => unreachable!("ledgers are loaded before recording"),
+        (Mode::Print, _) => None,
     }
 }
 
-macro_rules! run {
-    ($year:tt $($day:tt),*) => {
-        fn $year() -> Vec<Solution> {
-            vec![$(
-                Solution {
-                    year: stringify!($year).trim_start_matches("year").parse().unwrap(),
-                    day: stringify!($day).trim_start_matches("day").parse().unwrap(),
-                    wrapper: |data: &str| {
-                        use $year::$day::*;
+/// Load the ledger for a year's inputs, reporting unreadable files
+fn load_ledger(year: u32, input: &InputConfig) -> Option<Ledger> {
+    let Some(path) = Ledger::path_for_input(year, input) else {
+        eprintln!("No ledger for {year} answers from an explicit input");
+        return None;
+    };
+    match Ledger::load(&path) {
+        Ok(ledger) => Some(ledger),
+        Err(e) => {
+            eprintln!("Failed to read {}: {e}", path.display());
+            None
+        }
+    }
+}
 
-                        let input = parse(data);
-                        let part1 = part1(&input).to_string();
-                        let part2 = part2(&input).to_string();
+fn print_text(report: &Report, use_test: bool) {
+    let Report {
+        year, day, source, ..
+    } = report;
 
-                        (part1, part2)
+    match &report.result {
+        Ok(Answers {
+            part1,
+            part2,
+            timings,
+        }) => {
+            println!(
+                "{year} Day {day:02}{}",
+                if use_test { " (TEST)" } else { "" }
+            );
+            for (index, answer) in [part1, part2].into_iter().enumerate() {
+                match &report.statuses {
+                    Some(statuses) => {
+                        println!("    Part {}: {answer} [{}]", index + 1, statuses[index])
                     }
+                    None => println!("    Part {}: {answer}", index + 1),
                 }
-            ,)*]
+            }
+            println!(
+                "    Time: parse {:?}, part 1 {:?}, part 2 {:?}",
+                timings.parse, timings.part1, timings.part2
+            );
+        }
+        Err(Failure::MissingInput) => {
+            eprintln!("{year} Day {day:02}");
+            eprintln!("    Missing input!");
+            eprintln!("    Place input file in {source}");
+        }
+        Err(failure) => {
+            eprintln!("{year} Day {day:02}");
+            eprintln!("    {failure}");
+            eprintln!("    In {source}");
         }
     }
 }
 
-run!(year2025
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11
-);
+fn print_json(report: &Report) {
+    let fields = Fields::of(report);
+    let string = |value: Option<&str>| value.map_or("null".to_string(), json_string);
+    let nanos = |duration: Option<Duration>| {
+        duration.map_or("null".to_string(), |d| d.as_nanos().to_string())
+    };
+
+    println!(
+        "{{\"year\":{},\"day\":{},\"part1\":{},\"part2\":{},\"timings\":{{\"parse_ns\":{},\"part1_ns\":{},\"part2_ns\":{}}},\"part1_status\":{},\"part2_status\":{},\"input\":{},\"error\":{}}}",
+        report.year,
+        report.day,
+        string(fields.part1),
+        string(fields.part2),
+        nanos(fields.timings.map(|t| t.parse)),
+        nanos(fields.timings.map(|t| t.part1)),
+        nanos(fields.timings.map(|t| t.part2)),
+        string(fields.statuses[0]),
+        string(fields.statuses[1]),
+        json_string(&report.source.to_string()),
+        string(fields.error.as_deref()),
+    );
+}
+
+fn print_csv(report: &Report) {
+    let fields = Fields::of(report);
+    let string = |value: Option<&str>| value.map_or(String::new(), csv_string);
+    let nanos =
+        |duration: Option<Duration>| duration.map_or(String::new(), |d| d.as_nanos().to_string());
+
+    println!(
+        "{},{},{},{},{},{},{},{},{},{},{}",
+        report.year,
+        report.day,
+        string(fields.part1),
+        string(fields.part2),
+        nanos(fields.timings.map(|t| t.parse)),
+        nanos(fields.timings.map(|t| t.part1)),
+        nanos(fields.timings.map(|t| t.part2)),
+        string(fields.statuses[0]),
+        string(fields.statuses[1]),
+        csv_string(&report.source.to_string()),
+        string(fields.error.as_deref()),
+    );
+}
+
+/// Flattened view of a report for the machine-readable formats
+struct Fields<'a> {
+    part1: Option<&'a str>,
+    part2: Option<&'a str>,
+    timings: Option<Timings>,
+    statuses: [Option<&'static str>; 2],
+    error: Option<String>,
+}
+
+impl<'a> Fields<'a> {
+    fn of(report: &'a Report) -> Self {
+        let (part1, part2, timings, error) = match &report.result {
+            Ok(output) => (
+                Some(output.part1.as_str()),
+                Some(output.part2.as_str()),
+                Some(output.timings),
+                None,
+            ),
+            Err(failure) => (None, None, None, Some(failure.to_string())),
+        };
+        let statuses = [0, 1].map(|index| {
+            report
+                .statuses
+                .as_ref()
+                .map(|statuses| match statuses[index] {
+                    Status::Pass => "PASS",
+                    Status::Fail { .. } => "FAIL",
+                    Status::Unknown => "UNKNOWN",
+                })
+        });
+
+        Self {
+            part1,
+            part2,
+            timings,
+            statuses,
+            error,
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_string(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
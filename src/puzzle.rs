@@ -0,0 +1,96 @@
+use crate::utils::ParseError;
+use std::fmt::Display;
+
+/// A single day's puzzle, split into parsing and the two parts
+pub trait Puzzle {
+    const YEAR: u32;
+    const DAY: u32;
+
+    /// Parsed representation shared by both parts
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+/// Callback invoked once for every registered puzzle
+pub trait Visitor {
+    fn visit<P: Puzzle>(&mut self);
+}
+
+/// Number a module name such as `day07` or `aoc_25::year2025` ends in.
+///
+/// Used by [`register!`] to derive a puzzle's year and day from where it lives.
+#[doc(hidden)]
+pub const fn trailing_number(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut start = bytes.len();
+    while start > 0 && bytes[start - 1].is_ascii_digit() {
+        start -= 1;
+    }
+    assert!(start < bytes.len(), "module name must end in a number");
+
+    let mut number = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        number = number * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    number
+}
+
+/// Declare the day modules of a year and register their puzzles.
+///
+/// Each `dayXX => DayXX` entry declares the module `dayXX` and implements
+/// [`Puzzle`] for a new unit struct `DayXX` by forwarding to the module's
+/// `ParsedInput` type and free `parse`, `part1` and `part2` functions. The
+/// year and day are taken from the module names (`yearXXXX` and `dayXX`).
+///
+/// Also expands to a `visit` function that hands every listed puzzle to a
+/// [`Visitor`] in the order given.
+#[macro_export]
+macro_rules! register {
+    ($($day:ident => $puzzle:ident),* $(,)?) => {
+        $(
+            pub mod $day;
+
+            #[doc = concat!("Puzzle solved by [`", stringify!($day), "`]")]
+            pub struct $puzzle;
+
+            impl $crate::puzzle::Puzzle for $puzzle {
+                const YEAR: u32 = $crate::puzzle::trailing_number(module_path!());
+                const DAY: u32 = $crate::puzzle::trailing_number(stringify!($day));
+                type Input = $day::ParsedInput;
+
+                fn parse(input: &str) -> Result<Self::Input, $crate::utils::ParseError> {
+                    $day::parse(input)
+                }
+
+                fn part1(input: &Self::Input) -> impl std::fmt::Display {
+                    $day::part1(input)
+                }
+
+                fn part2(input: &Self::Input) -> impl std::fmt::Display {
+                    $day::part2(input)
+                }
+            }
+        )*
+
+        /// Visit every puzzle of this year in day order
+        pub fn visit<V: $crate::puzzle::Visitor>(visitor: &mut V) {
+            $(visitor.visit::<$puzzle>();)*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_number() {
+        assert_eq!(trailing_number("day07"), 7);
+        assert_eq!(trailing_number("aoc_25::year2025"), 2025);
+    }
+}
@@ -0,0 +1,155 @@
+//! Run registered puzzles by year and day without going through the CLI.
+//!
+//! ```
+//! let answers = aoc_25::runner::solve(2025, 1, "L68\nR48\n").unwrap();
+//! println!("{} {}", answers.part1, answers.part2);
+//! ```
+use crate::puzzle::{Puzzle, Visitor};
+use crate::utils::ParseError;
+use std::error::Error;
+use std::fmt;
+use std::ops::AddAssign;
+use std::time::{Duration, Instant};
+
+/// Answers to both parts of a puzzle together with the time spent in each phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+    pub timings: Timings,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+impl AddAssign for Timings {
+    fn add_assign(&mut self, other: Timings) {
+        self.parse += other.parse;
+        self.part1 += other.part1;
+        self.part2 += other.part2;
+    }
+}
+
+/// Error returned by [`solve`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// No puzzle is registered for this year and day
+    UnknownPuzzle {
+        year: u32,
+        day: u32,
+    },
+    Parse(ParseError),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownPuzzle { year, day } => {
+                write!(f, "no solution for {year} day {day:02}")
+            }
+            SolveError::Parse(e) => write!(f, "parse error at {e}"),
+        }
+    }
+}
+
+impl Error for SolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolveError::UnknownPuzzle { .. } => None,
+            SolveError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> Self {
+        SolveError::Parse(e)
+    }
+}
+
+/// A registered puzzle with its type erased, so it can be stored and picked at runtime
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    wrapper: fn(&str) -> Result<Answers, ParseError>,
+}
+
+impl Solution {
+    /// Parse `input` and solve both parts, timing each phase
+    pub fn solve(&self, input: &str) -> Result<Answers, ParseError> {
+        (self.wrapper)(input)
+    }
+}
+
+impl fmt::Debug for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Solution")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Every registered puzzle, ordered by year then day
+pub fn puzzles() -> impl Iterator<Item = Solution> {
+    let mut solutions = Solutions(Vec::new());
+    crate::visit_puzzles(&mut solutions);
+    solutions.0.into_iter()
+}
+
+/// Look up the puzzle registered for a year and day
+pub fn find(year: u32, day: u32) -> Option<Solution> {
+    puzzles().find(|s| s.year == year && s.day == day)
+}
+
+/// Solve both parts of a year and day's puzzle for `input`
+pub fn solve(year: u32, day: u32, input: &str) -> Result<Answers, SolveError> {
+    let solution = find(year, day).ok_or(SolveError::UnknownPuzzle { year, day })?;
+    Ok(solution.solve(input)?)
+}
+
+/// Collects every registered puzzle into a type-erased [`Solution`]
+struct Solutions(Vec<Solution>);
+
+impl Visitor for Solutions {
+    fn visit<P: Puzzle>(&mut self) {
+        self.0.push(Solution {
+            year: P::YEAR,
+            day: P::DAY,
+            wrapper: |data: &str| {
+                let instant = Instant::now();
+                let input = P::parse(data)?;
+                let parse = instant.elapsed();
+
+                let instant = Instant::now();
+                let part1 = P::part1(&input).to_string();
+                let part1_time = instant.elapsed();
+
+                let instant = Instant::now();
+                let part2 = P::part2(&input).to_string();
+                let part2_time = instant.elapsed();
+
+                Ok(Answers {
+                    part1,
+                    part2,
+                    timings: Timings {
+                        parse,
+                        part1: part1_time,
+                        part2: part2_time,
+                    },
+                })
+            },
+        });
+    }
+}
@@ -0,0 +1,322 @@
+//! Generates a new day from a template and wires it into every registration point:
+//! the year's `register!` list, the year modules in `lib.rs`, and the test suite.
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Create the source, test and example input files for a day, returning every path touched.
+///
+/// Every change is worked out before anything is written, so a day that is
+/// partly there already or an unrecognised registration file leaves the tree untouched.
+pub fn new_day(root: &Path, year: u32, day: u32) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("day must be between 1 and 25, got {day}"),
+        ));
+    }
+
+    let mut changes = Changes::default();
+
+    // Solution source and its registration
+    changes.create(
+        root.join(format!("src/year{year}/day{day:02}.rs")),
+        SOURCE_TEMPLATE,
+    );
+    let year_mod = root.join(format!("src/year{year}/mod.rs"));
+    if year_mod.exists() {
+        changes.update(&year_mod, |text| {
+            insert_line(
+                text,
+                &format!("    day{day:02} => Day{day:02},"),
+                |line| is_registration(line.trim()),
+                text.lines()
+                    .position(|line| line.contains("register!"))
+                    .map(|i| i + 1),
+            )
+        })?;
+    } else {
+        changes.create(
+            year_mod,
+            &format!("crate::register! {{\n    day{day:02} => Day{day:02},\n}}\n"),
+        );
+        changes.update(&root.join("src/lib.rs"), |text| {
+            let text = insert_line(
+                text,
+                &format!("pub mod year{year};"),
+                |line| line.starts_with("pub mod year"),
+                None,
+            )?;
+            insert_line(
+                &text,
+                &format!("    year{year}::visit(visitor);"),
+                |line| {
+                    line.trim().starts_with("year") && line.trim().ends_with("::visit(visitor);")
+                },
+                None,
+            )
+        })?;
+    }
+
+    // Tests, with an empty example input for the registry test to pick up
+    changes.create(
+        root.join(format!("tests/year{year}/day{day:02}.rs")),
+        &test_template(year, day),
+    );
+    changes.create(
+        root.join(format!("tests/year{year}/day{day:02}_input.txt")),
+        "",
+    );
+    let test_mod = root.join(format!("tests/year{year}/mod.rs"));
+    if test_mod.exists() {
+        changes.update(&test_mod, |text| {
+            insert_line(
+                text,
+                &format!("pub mod day{day:02};"),
+                |line| line.starts_with("pub mod day"),
+                Some(0),
+            )
+        })?;
+    } else {
+        changes.create(test_mod, &format!("pub mod day{day:02};\n"));
+        changes.update(&root.join("tests/integration_tests.rs"), |text| {
+            insert_line(
+                text,
+                &format!("mod year{year};"),
+                |line| line.starts_with("mod year"),
+                None,
+            )
+        })?;
+    }
+
+    changes.apply()
+}
+
+fn is_registration(line: &str) -> bool {
+    line.starts_with("day") && line.contains(" => Day") && line.ends_with(',')
+}
+
+/// Files to create and rewrite, collected up front so nothing is written until all of them are known
+#[derive(Default)]
+struct Changes {
+    created: Vec<(PathBuf, String)>,
+    updated: Vec<(PathBuf, String)>,
+}
+
+impl Changes {
+    fn create(&mut self, path: PathBuf, contents: &str) {
+        self.created.push((path, contents.to_string()));
+    }
+
+    /// Queue `edit` applied to the current contents of `path`, if that changes anything
+    fn update(
+        &mut self,
+        path: &Path,
+        edit: impl FnOnce(&str) -> io::Result<String>,
+    ) -> io::Result<()> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        let updated = edit(&text)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        if updated != text {
+            self.updated.push((path.to_path_buf(), updated));
+        }
+        Ok(())
+    }
+
+    fn apply(self) -> io::Result<Vec<PathBuf>> {
+        // Never overwrite a file, e.g. an example input pasted in by hand
+        if let Some((path, _)) = self.created.iter().find(|(path, _)| path.exists()) {
+            return Err(io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            ));
+        }
+
+        let mut touched = Vec::new();
+        for (path, contents) in self.created {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // `create_new` still refuses if the file appeared since the check above
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)?
+                .write_all(contents.as_bytes())?;
+            touched.push(path);
+        }
+        for (path, contents) in self.updated {
+            fs::write(&path, contents)?;
+            touched.push(path);
+        }
+        Ok(touched)
+    }
+}
+
+/// Insert `new_line` in sorted position among the lines matching `is_peer`.
+///
+/// Falls back to line index `fallback` when there are no peers yet, and does
+/// nothing if the line is already present.
+fn insert_line(
+    text: &str,
+    new_line: &str,
+    is_peer: impl Fn(&str) -> bool,
+    fallback: Option<usize>,
+) -> io::Result<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.contains(&new_line) {
+        return Ok(text.to_string());
+    }
+
+    let peers: Vec<usize> = (0..lines.len()).filter(|&i| is_peer(lines[i])).collect();
+    let index = match peers.iter().find(|&&i| lines[i] > new_line) {
+        Some(&i) => i,
+        None => match (peers.last(), fallback) {
+            (Some(&last), _) => last + 1,
+            (None, Some(fallback)) => fallback.min(lines.len()),
+            (None, None) => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("can't find where to insert `{}`", new_line.trim()),
+                ))
+            }
+        },
+    };
+
+    lines.insert(index, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+const SOURCE_TEMPLATE: &str = r#"use crate::utils::ParseError;
+
+pub type ParsedInput = Vec<String>;
+
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+    // TODO: Implement parsing
+    Ok(input.lines().map(|s| s.to_string()).collect())
+}
+
+pub fn part1(_input: &ParsedInput) -> &'static str {
+    // TODO: Implement part 1
+    "Not implemented"
+}
+
+pub fn part2(_input: &ParsedInput) -> &'static str {
+    // TODO: Implement part 2
+    "Not implemented"
+}
+"#;
+
+fn test_template(year: u32, day: u32) -> String {
+    format!(
+        r#"use aoc_25::year{year}::day{day:02}::*;
+
+const INPUT: &str = include_str!("day{day:02}_input.txt");
+
+#[test]
+#[ignore] // Remove when you have expected values
+fn part1_test() {{
+    let parsed = parse(INPUT).unwrap();
+    assert_eq!(part1(&parsed), "expected_result");
+}}
+
+#[test]
+#[ignore] // Remove when you have expected values
+fn part2_test() {{
+    let parsed = parse(INPUT).unwrap();
+    assert_eq!(part2(&parsed), "expected_result");
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("aoc-25-scaffold-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/year2025")).unwrap();
+        fs::create_dir_all(root.join("tests/year2025")).unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            "pub mod utils;\npub mod year2025;\n\npub fn visit_puzzles<V: Visitor>(visitor: &mut V) {\n    year2025::visit(visitor);\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/year2025/mod.rs"),
+            "crate::register! {\n    day01 => Day01,\n    day03 => Day03,\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("tests/integration_tests.rs"),
+            "mod registry;\nmod year2025;\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("tests/year2025/mod.rs"),
+            "pub mod day01;\npub mod day03;\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_existing_year() {
+        let root = temp_root("existing");
+        new_day(&root, 2025, 2).unwrap();
+
+        let year_mod = fs::read_to_string(root.join("src/year2025/mod.rs")).unwrap();
+        assert_eq!(
+            year_mod,
+            "crate::register! {\n    day01 => Day01,\n    day02 => Day02,\n    day03 => Day03,\n}\n"
+        );
+        let test_mod = fs::read_to_string(root.join("tests/year2025/mod.rs")).unwrap();
+        assert_eq!(test_mod, "pub mod day01;\npub mod day02;\npub mod day03;\n");
+        assert!(root.join("src/year2025/day02.rs").exists());
+        assert!(root.join("tests/year2025/day02_input.txt").exists());
+
+        // Refuses to overwrite an existing day
+        assert!(new_day(&root, 2025, 2).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_existing_files_untouched() {
+        let root = temp_root("untouched");
+        let example = root.join("tests/year2025/day02_input.txt");
+        fs::write(&example, "pasted example").unwrap();
+
+        let error = new_day(&root, 2025, 2).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&example).unwrap(), "pasted example");
+        // Nothing was registered or created before the conflict was found
+        assert!(!root.join("src/year2025/day02.rs").exists());
+        let year_mod = fs::read_to_string(root.join("src/year2025/mod.rs")).unwrap();
+        assert!(!year_mod.contains("day02"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_new_year() {
+        let root = temp_root("new");
+        new_day(&root, 2024, 1).unwrap();
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod year2024;\npub mod year2025;"));
+        assert!(lib.contains("    year2024::visit(visitor);\n    year2025::visit(visitor);"));
+        let tests = fs::read_to_string(root.join("tests/integration_tests.rs")).unwrap();
+        assert_eq!(tests, "mod registry;\nmod year2024;\nmod year2025;\n");
+        assert!(root.join("src/year2024/mod.rs").exists());
+        assert!(root.join("tests/year2024/mod.rs").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_day() {
+        assert!(new_day(Path::new("."), 2025, 26).is_err());
+    }
+}
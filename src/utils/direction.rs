@@ -0,0 +1,111 @@
+use super::point::Point;
+
+/// Compass direction on a grid where `y` grows downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The 4 cardinal directions, clockwise from up
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The 4 diagonal directions, clockwise from up-right
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    /// All 8 directions, clockwise from up
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Offset of a single step in this direction
+    pub const fn delta(self) -> Point {
+        let (x, y) = match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        };
+        Point { x, y }
+    }
+
+    /// Rotate 90 degrees clockwise
+    pub const fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Rotate 90 degrees counter-clockwise
+    pub const fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Rotate 45 degrees clockwise
+    pub const fn turn_right45(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Rotate 45 degrees counter-clockwise
+    pub const fn turn_left45(self) -> Self {
+        self.rotate(7)
+    }
+
+    /// Direction pointing the other way
+    pub const fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        (self as usize) % 2 == 1
+    }
+
+    /// Rotate clockwise by `eighths` of a full turn
+    const fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.turn_right45(), Direction::Up);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.delta() + dir.opposite().delta(), Point::new(0, 0));
+            assert_eq!(dir.is_diagonal(), Direction::DIAGONAL.contains(&dir));
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Disjoint-set union (union-find) over the indices `0..n`.
+//!
+//! Union by size keeps every tree logarithmically shallow. A plain set also
+//! compresses paths in [`find`](DisjointSet::find); a set built with
+//! [`with_rollback`](DisjointSet::with_rollback) records its unions instead so
+//! they can be undone, which path compression would make impossible.
+
+/// Position in a [`DisjointSet`]'s history to [`rollback`](DisjointSet::rollback) to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    /// Size of each root's component (stale for non-roots)
+    size: Vec<usize>,
+    components: usize,
+    /// `(child, root)` of every union, only kept by sets that can roll back
+    history: Option<Vec<(usize, usize)>>,
+}
+
+impl DisjointSet {
+    /// `n` singleton components
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+            history: None,
+        }
+    }
+
+    /// `n` singleton components whose unions can be undone with [`rollback`](Self::rollback)
+    pub fn with_rollback(n: usize) -> Self {
+        Self {
+            history: Some(Vec::new()),
+            ..Self::new(n)
+        }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of components
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Representative of `x`'s component, compressing the path unless rollback is enabled
+    pub fn find(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+        if self.history.is_none() {
+            let mut node = x;
+            while node != root {
+                node = std::mem::replace(&mut self.parent[node], root);
+            }
+        }
+        root
+    }
+
+    /// Representative of `x`'s component without modifying the set
+    pub fn root(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Merge the components of `a` and `b`, returning false if they were already one
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // Union by size: hang the smaller tree under the larger
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        if let Some(history) = &mut self.history {
+            history.push((b, a));
+        }
+        true
+    }
+
+    /// Size of `x`'s component
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Size of every component, in order of their representatives
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+    }
+
+    /// Members of every component, ordered by their smallest member
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for x in 0..self.len() {
+            let root = self.root(x);
+            if index[root] == usize::MAX {
+                index[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[index[root]].push(x);
+        }
+        components
+    }
+
+    /// Current position in the history (panics unless built with [`with_rollback`](Self::with_rollback))
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.history().len())
+    }
+
+    /// Undo every union made since `checkpoint`
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        while self.history().len() > checkpoint.0 {
+            let (child, root) = self.history.as_mut().unwrap().pop().unwrap();
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.components += 1;
+        }
+    }
+
+    fn history(&self) -> &[(usize, usize)] {
+        self.history
+            .as_deref()
+            .expect("rollback needs a set built with DisjointSet::with_rollback")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(set.union(4, 5));
+        assert!(!set.union(0, 2));
+
+        assert!(set.same(0, 2) && !set.same(0, 3));
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.size(2), 3);
+        assert_eq!(set.component_sizes().collect::<Vec<_>>(), [3, 1, 2]);
+        assert_eq!(set.components(), [vec![0, 1, 2], vec![3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut set = DisjointSet::with_rollback(5);
+        set.union(0, 1);
+        let checkpoint = set.checkpoint();
+        set.union(2, 3);
+        set.union(1, 3);
+        assert_eq!((set.component_count(), set.size(0)), (2, 4));
+
+        set.rollback(checkpoint);
+        assert_eq!(set.component_count(), 4);
+        assert_eq!(set.components(), [vec![0, 1], vec![2], vec![3], vec![4]]);
+        assert_eq!(set.size(1), 2);
+
+        // Rolling back to an older checkpoint after more unions still works
+        set.union(3, 4);
+        set.rollback(Checkpoint(0));
+        assert_eq!(set.component_count(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "with_rollback")]
+    fn test_checkpoint_needs_rollback() {
+        DisjointSet::new(2).checkpoint();
+    }
+}
@@ -0,0 +1,452 @@
+//! Directed graphs over string node names, interned to dense indices.
+//!
+//! Nodes are referred to by their `usize` index everywhere except [`Graph::intern`]
+//! and [`Graph::id`], so algorithms can use plain vectors instead of maps.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    /// Outgoing edges of each node
+    edges: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the node called `name`, adding it if it's new
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    /// Index of the node called `name`, if there is one
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.edges[from].push(to);
+    }
+
+    /// Nodes with an edge from `id`
+    pub fn neighbors(&self, id: usize) -> &[usize] {
+        &self.edges[id]
+    }
+
+    /// Nodes in an order where every edge points forwards, None if there is a cycle
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        // Kahn's algorithm: repeatedly take a node nothing left points to
+        let mut incoming = vec![0; self.len()];
+        for &to in self.edges.iter().flatten() {
+            incoming[to] += 1;
+        }
+        let mut order: Vec<usize> = (0..self.len()).filter(|&id| incoming[id] == 0).collect();
+        let mut next = 0;
+        while let Some(&id) = order.get(next) {
+            next += 1;
+            for &to in &self.edges[id] {
+                incoming[to] -= 1;
+                if incoming[to] == 0 {
+                    order.push(to);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Nodes of some cycle in edge order, None if the graph is acyclic
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        for start in 0..self.len() {
+            if state[start] != State::New {
+                continue;
+            }
+            // Depth-first, keeping the current path with the next edge to try from each node
+            let mut path = vec![(start, 0)];
+            state[start] = State::OnPath;
+            while let Some(&(id, edge)) = path.last() {
+                let Some(&next) = self.edges[id].get(edge) else {
+                    state[id] = State::Done;
+                    path.pop();
+                    continue;
+                };
+                path.last_mut().unwrap().1 += 1;
+                match state[next] {
+                    State::New => {
+                        state[next] = State::OnPath;
+                        path.push((next, 0));
+                    }
+                    State::OnPath => {
+                        let first = path.iter().position(|&(id, _)| id == next).unwrap();
+                        return Some(path[first..].iter().map(|&(id, _)| id).collect());
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Strongly connected components, each listed after every component it has edges into
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // Tarjan's algorithm, with an explicit call stack
+        const UNVISITED: usize = usize::MAX;
+        let mut index = vec![UNVISITED; self.len()];
+        let mut low = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for start in 0..self.len() {
+            if index[start] != UNVISITED {
+                continue;
+            }
+            let mut calls = vec![(start, 0)];
+            index[start] = counter;
+            low[start] = counter;
+            counter += 1;
+            stack.push(start);
+            on_stack[start] = true;
+
+            while let Some(&(id, edge)) = calls.last() {
+                if let Some(&next) = self.edges[id].get(edge) {
+                    calls.last_mut().unwrap().1 += 1;
+                    if index[next] == UNVISITED {
+                        index[next] = counter;
+                        low[next] = counter;
+                        counter += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        calls.push((next, 0));
+                    } else if on_stack[next] {
+                        low[id] = low[id].min(index[next]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[id]);
+                }
+                if low[id] == index[id] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == id {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Which nodes can be reached from `from` (including itself)
+    pub fn reachable(&self, from: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        seen[from] = true;
+        let mut stack = vec![from];
+        while let Some(id) = stack.pop() {
+            for &next in &self.edges[id] {
+                if !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    pub fn reaches(&self, from: usize, to: usize) -> bool {
+        self.reachable(from)[to]
+    }
+
+    /// Path counts between any two nodes
+    pub fn path_counter(&self) -> PathCounter<'_> {
+        let mut incoming = vec![Vec::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &to in edges {
+                incoming[to].push(from);
+            }
+        }
+        PathCounter {
+            graph: self,
+            incoming,
+            counts: HashMap::new(),
+        }
+    }
+}
+
+/// Why [`PathCounter::count`] has no answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathCountError {
+    /// Nodes of a cycle, in edge order, that paths can go round forever
+    Cycle(Vec<usize>),
+    /// More paths than fit in a `u64`
+    Overflow,
+}
+
+impl fmt::Display for PathCountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathCountError::Cycle(_) => write!(f, "a cycle gives infinitely many paths"),
+            PathCountError::Overflow => write!(f, "more paths than fit in a u64"),
+        }
+    }
+}
+
+impl Error for PathCountError {}
+
+/// What a [`PathCounter`] knows about the paths from a node to one target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Count {
+    /// The target can't be reached from here
+    Never,
+    Unknown,
+    /// Being counted, so reaching it again means a cycle
+    OnPath,
+    Known(u64),
+}
+
+/// Counts paths in a [`Graph`], remembering the counts towards every target it was asked about.
+///
+/// Only cycles that a path between the two nodes can go round make the count
+/// fail, cycles elsewhere in the graph are ignored.
+#[derive(Debug, Clone)]
+pub struct PathCounter<'a> {
+    graph: &'a Graph,
+    /// Incoming edges of each node
+    incoming: Vec<Vec<usize>>,
+    /// Paths from every node to the key
+    counts: HashMap<usize, Vec<Count>>,
+}
+
+impl PathCounter<'_> {
+    /// Number of distinct paths from `from` to `to` (1 if they are the same node).
+    ///
+    /// Paths end on reaching `to`, so cycles through `to` itself don't count.
+    pub fn count(&mut self, from: usize, to: usize) -> Result<u64, PathCountError> {
+        let (graph, incoming) = (self.graph, &self.incoming);
+        let counts = self.counts.entry(to).or_insert_with(|| {
+            // Everything that can reach `to` has a count to work out, everything else has none
+            let mut counts = vec![Count::Never; graph.len()];
+            counts[to] = Count::Known(1);
+            let mut stack = vec![to];
+            while let Some(id) = stack.pop() {
+                for &previous in &incoming[id] {
+                    if counts[previous] == Count::Never {
+                        counts[previous] = Count::Unknown;
+                        stack.push(previous);
+                    }
+                }
+            }
+            counts
+        });
+
+        let result = count_from(graph, counts, from);
+        if result.is_err() {
+            // Forget the half-counted path so later queries start afresh
+            for count in counts.iter_mut().filter(|count| **count == Count::OnPath) {
+                *count = Count::Unknown;
+            }
+        }
+        result
+    }
+}
+
+/// Fill in `counts` for `from` and everything it leads to, depth-first
+fn count_from(graph: &Graph, counts: &mut [Count], from: usize) -> Result<u64, PathCountError> {
+    match counts[from] {
+        Count::Never => return Ok(0),
+        Count::Known(total) => return Ok(total),
+        Count::Unknown | Count::OnPath => {}
+    }
+
+    // The current path, with the next edge to try and the paths found so far from each node
+    let mut path = vec![(from, 0, 0u64)];
+    counts[from] = Count::OnPath;
+    while let Some(&(id, edge, total)) = path.last() {
+        let Some(&next) = graph.edges[id].get(edge) else {
+            counts[id] = Count::Known(total);
+            path.pop();
+            if let Some(parent) = path.last_mut() {
+                parent.2 = parent
+                    .2
+                    .checked_add(total)
+                    .ok_or(PathCountError::Overflow)?;
+            }
+            continue;
+        };
+        let top = path.last_mut().unwrap();
+        top.1 += 1;
+        match counts[next] {
+            Count::Never => {}
+            Count::Known(paths) => {
+                top.2 = total.checked_add(paths).ok_or(PathCountError::Overflow)?;
+            }
+            Count::Unknown => {
+                counts[next] = Count::OnPath;
+                path.push((next, 0, 0));
+            }
+            Count::OnPath => {
+                let first = path.iter().position(|&(id, ..)| id == next).unwrap();
+                return Err(PathCountError::Cycle(
+                    path[first..].iter().map(|&(id, ..)| id).collect(),
+                ));
+            }
+        }
+    }
+    match counts[from] {
+        Count::Known(total) => Ok(total),
+        _ => unreachable!("the walk finishes where it started"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            let (from, to) = (graph.intern(from), graph.intern(to));
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    fn names(graph: &Graph, ids: &[usize]) -> Vec<String> {
+        ids.iter().map(|&id| graph.name(id).to_string()).collect()
+    }
+
+    #[test]
+    fn test_interning() {
+        let mut g = graph(&[("a", "b"), ("b", "c"), ("a", "c")]);
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.intern("b"), 1);
+        assert_eq!(g.id("c"), Some(2));
+        assert_eq!(g.id("d"), None);
+        assert_eq!(names(&g, g.neighbors(0)), ["b", "c"]);
+    }
+
+    #[test]
+    fn test_dag() {
+        let g = graph(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("d", "e"),
+            ("x", "b"),
+        ]);
+        let order = g.topological_sort().unwrap();
+        let position = |name| order.iter().position(|&id| id == g.id(name).unwrap());
+        assert!(position("a") < position("b") && position("b") < position("d"));
+        assert!(position("x") < position("b") && position("d") < position("e"));
+        assert_eq!(g.find_cycle(), None);
+
+        let id = |name| g.id(name).unwrap();
+        let mut paths = g.path_counter();
+        assert_eq!(paths.count(id("a"), id("e")), Ok(2));
+        assert_eq!(paths.count(id("x"), id("e")), Ok(1));
+        assert_eq!(paths.count(id("e"), id("a")), Ok(0));
+        assert_eq!(paths.count(id("d"), id("d")), Ok(1));
+
+        assert!(g.reaches(id("x"), id("e")) && !g.reaches(id("x"), id("c")));
+        assert_eq!(g.strongly_connected_components().len(), g.len());
+    }
+
+    #[test]
+    fn test_cycles() {
+        let g = graph(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+        ]);
+        assert_eq!(g.topological_sort(), None);
+
+        let cycle = g.find_cycle().unwrap();
+        assert_eq!(names(&g, &cycle), ["a", "b", "c"]);
+
+        // Only cycles on the way to the target matter
+        let id = |name| g.id(name).unwrap();
+        let mut paths = g.path_counter();
+        let Err(PathCountError::Cycle(cycle)) = paths.count(id("a"), id("d")) else {
+            panic!("a, b and c form a cycle on the way to d");
+        };
+        assert_eq!(names(&g, &cycle), ["a", "b", "c"]);
+        assert_eq!(paths.count(id("d"), id("e")), Ok(1));
+        assert_eq!(paths.count(id("c"), id("c")), Ok(1));
+        assert!(paths.count(id("b"), id("e")).is_err());
+
+        // Sink components come first
+        let components: Vec<Vec<String>> = g
+            .strongly_connected_components()
+            .iter()
+            .map(|component| {
+                let mut names = names(&g, component);
+                names.sort();
+                names
+            })
+            .collect();
+        assert_eq!(components, [vec!["d", "e"], vec!["a", "b", "c"]]);
+    }
+
+    #[test]
+    fn test_path_count_overflow() {
+        // Each diamond doubles the number of paths
+        let mut g = Graph::new();
+        let mut last = g.intern("0");
+        for i in 0..64 {
+            let (left, right) = (g.intern(&format!("{i}l")), g.intern(&format!("{i}r")));
+            let next = g.intern(&(i + 1).to_string());
+            for (from, to) in [(last, left), (last, right), (left, next), (right, next)] {
+                g.add_edge(from, to);
+            }
+            last = next;
+        }
+        let (start, middle) = (g.id("0").unwrap(), g.id("63").unwrap());
+        let mut paths = g.path_counter();
+        assert_eq!(paths.count(start, middle), Ok(1 << 63));
+        assert_eq!(paths.count(start, last), Err(PathCountError::Overflow));
+    }
+}
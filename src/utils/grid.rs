@@ -1,52 +1,184 @@
+pub mod automaton;
+pub mod regions;
+pub mod search;
+mod view;
+
+pub use view::GridView;
+
 use super::point::Point;
+use std::error::Error;
 use std::fmt;
+use std::ops::{Index, IndexMut};
 
-#[derive(Debug, Clone)]
+/// Rectangular grid stored row-major in a single vector
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Grid<T> {
-    pub cells: Vec<Vec<T>>,
+    /// Cells in row-major order, `width * height` of them. Private so the layout
+    /// can't be mistaken for rows, see [`Grid::row`], [`Grid::rows`] and indexing by [`Point`]
+    cells: Vec<T>,
     pub width: usize,
     pub height: usize,
 }
 
+/// Error returned when building a grid from rows of differing lengths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedRowError {
+    /// 0-based index of the first row whose length differs from the first row
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for RaggedRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, expected {}",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+impl Error for RaggedRowError {}
+
 impl<T: Clone> Grid<T> {
     /// Create a new grid with a default value
     pub fn new(width: usize, height: usize, default: T) -> Self {
         Self {
-            cells: vec![vec![default; width]; height],
+            cells: vec![default; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Create a grid from a 2D vector, padding short rows with `fill` up to the longest row
+    pub fn from_vec_padded(rows: Vec<Vec<T>>, fill: T) -> Self {
+        let height = rows.len();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+        Self {
+            cells,
             width,
             height,
         }
     }
 
-    /// Create a grid from a 2D vector
-    pub fn from_vec(cells: Vec<Vec<T>>) -> Self {
-        let height = cells.len();
-        let width = if height > 0 { cells[0].len() } else { 0 };
+    /// Create a grid from a 2D vector, padding short rows with `T::default()`
+    #[deprecated(
+        note = "use `try_from_vec` to reject ragged rows or `from_vec_padded` to pick the fill"
+    )]
+    pub fn from_vec(rows: Vec<Vec<T>>) -> Self
+    where
+        T: Default,
+    {
+        Self::from_vec_padded(rows, T::default())
+    }
+
+    /// Swap rows and columns
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotate 90 degrees clockwise
+    pub fn rotate_90(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    /// Rotate 180 degrees
+    pub fn rotate_180(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| {
+            (self.width - 1 - x, self.height - 1 - y)
+        })
+    }
+
+    /// Rotate 270 degrees clockwise (90 degrees counter-clockwise)
+    pub fn rotate_270(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    /// Build a `width` x `height` grid whose cell `(x, y)` is copied from `source(x, y)` in this one
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (sx, sy) = source(x, y);
+                self.cells[sy * self.width + sx].clone()
+            })
+            .collect();
         Self {
             cells,
             width,
             height,
         }
     }
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from a 2D vector, rejecting rows that differ in length
+    pub fn try_from_vec(rows: Vec<Vec<T>>) -> Result<Self, RaggedRowError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some((row, found)) = rows
+            .iter()
+            .map(Vec::len)
+            .enumerate()
+            .find(|&(_, len)| len != width)
+        {
+            return Err(RaggedRowError {
+                row,
+                expected: width,
+                found,
+            });
+        }
+
+        Ok(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// Take the cells back out as one vector per row
+    pub fn into_rows(self) -> Vec<Vec<T>> {
+        let mut cells = self.cells.into_iter();
+        (0..self.height)
+            .map(|_| cells.by_ref().take(self.width).collect())
+            .collect()
+    }
+
+    /// Offset of a point into `cells` (returns None if out of bounds)
+    fn offset(&self, point: &Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
 
     /// Get a value at a point (returns None if out of bounds)
     pub fn get(&self, point: &Point) -> Option<&T> {
-        if point.x < 0 || point.y < 0 {
-            return None;
-        }
-        let x = point.x as usize;
-        let y = point.y as usize;
-        self.cells.get(y).and_then(|row| row.get(x))
+        self.offset(point).map(|i| &self.cells[i])
     }
 
     /// Get a mutable reference to a value at a point
     pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
-        if point.x < 0 || point.y < 0 {
-            return None;
-        }
-        let x = point.x as usize;
-        let y = point.y as usize;
-        self.cells.get_mut(y).and_then(|row| row.get_mut(x))
+        self.offset(point).map(|i| &mut self.cells[i])
     }
 
     /// Set a value at a point (returns false if out of bounds)
@@ -61,28 +193,78 @@ impl<T: Clone> Grid<T> {
 
     /// Check if a point is within bounds
     pub fn contains(&self, point: &Point) -> bool {
-        point.x >= 0
-            && point.y >= 0
-            && (point.x as usize) < self.width
-            && (point.y as usize) < self.height
+        in_bounds(point, self.width, self.height)
+    }
+
+    /// Cells of row `y` (panics if out of bounds)
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Mutable cells of row `y` (panics if out of bounds)
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterate over the rows from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Borrowed view of the whole grid
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView::new(self)
+    }
+
+    /// Borrowed view of the rectangle at `(x, y)` (returns None if it doesn't fit)
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> Option<GridView<'_, T>> {
+        self.as_view().view(x, y, width, height)
+    }
+
+    /// Split into blocks separated by columns where every cell is blank
+    pub fn split_columns(&self, is_blank: impl Fn(&T) -> bool) -> Vec<GridView<'_, T>> {
+        self.as_view().split_columns(is_blank)
+    }
+
+    /// Split into blocks separated by rows where every cell is blank
+    pub fn split_rows(&self, is_blank: impl Fn(&T) -> bool) -> Vec<GridView<'_, T>> {
+        self.as_view().split_rows(is_blank)
     }
 
     /// Get all valid neighbors (4-directional) of a point
-    pub fn neighbors4(&self, point: &Point) -> Vec<Point> {
+    pub fn neighbors4(&self, point: &Point) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
         point
             .neighbors4()
             .into_iter()
-            .filter(|p| self.contains(p))
-            .collect()
+            .filter(move |p| in_bounds(p, width, height))
     }
 
     /// Get all valid neighbors (8-directional) of a point
-    pub fn neighbors8(&self, point: &Point) -> Vec<Point> {
+    pub fn neighbors8(&self, point: &Point) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
         point
             .neighbors8()
             .into_iter()
-            .filter(|p| self.contains(p))
-            .collect()
+            .filter(move |p| in_bounds(p, width, height))
+    }
+
+    /// Valid neighbors (4-directional) of a point together with their values
+    pub fn neighbor_cells4(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        point
+            .neighbors4()
+            .into_iter()
+            .filter_map(move |p| self.get(&p).map(|cell| (p, cell)))
+    }
+
+    /// Valid neighbors (8-directional) of a point together with their values
+    pub fn neighbor_cells8(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        point
+            .neighbors8()
+            .into_iter()
+            .filter_map(move |p| self.get(&p).map(|cell| (p, cell)))
     }
 
     /// Iterate over all points in the grid
@@ -93,8 +275,7 @@ impl<T: Clone> Grid<T> {
 
     /// Iterate over all cells with their positions
     pub fn cells_with_points(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
-        self.points()
-            .filter_map(move |p| self.get(&p).map(|cell| (p, cell)))
+        self.points().zip(&self.cells)
     }
 
     /// Find all positions where a predicate is true
@@ -119,17 +300,49 @@ impl<T: Clone> Grid<T> {
     }
 }
 
+fn in_bounds(point: &Point, width: usize, height: usize) -> bool {
+    point.x >= 0 && point.y >= 0 && (point.x as usize) < width && (point.y as usize) < height
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// Value at a point (panics if out of bounds)
+    fn index(&self, point: Point) -> &T {
+        match self.offset(&point) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "{point:?} out of bounds for a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.offset(&point) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "{point:?} out of bounds for a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
 impl Grid<char> {
-    /// Create a character grid from a string (lines separated by newlines)
+    /// Create a character grid from a string (lines separated by newlines),
+    /// padding short lines with spaces
     pub fn from_string(input: &str) -> Self {
-        let cells: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-        Self::from_vec(cells)
+        let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        Self::from_vec_padded(rows, ' ')
     }
 
     /// Convert grid back to a string
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
-        self.cells
-            .iter()
+        self.rows()
             .map(|row| row.iter().collect::<String>())
             .collect::<Vec<_>>()
             .join("\n")
@@ -138,7 +351,7 @@ impl Grid<char> {
 
 impl<T: fmt::Display> fmt::Display for Grid<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in &self.cells {
+        for row in self.rows() {
             for cell in row {
                 write!(f, "{}", cell)?;
             }
@@ -169,4 +382,67 @@ mod tests {
         assert_eq!(grid.get(&Point::new(0, 0)), Some(&'a'));
         assert_eq!(grid.get(&Point::new(2, 1)), Some(&'f'));
     }
+
+    #[test]
+    fn test_rows_and_index() {
+        let mut grid = Grid::try_from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid[Point::new(2, 0)], 3);
+        grid[Point::new(0, 1)] = 9;
+        grid.row_mut(0)[1] = 7;
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [&[1, 7, 3], &[9, 5, 6]]);
+        assert_eq!(grid.get(&Point::new(3, 0)), None);
+        assert_eq!(grid.get(&Point::new(-1, 1)), None);
+        assert_eq!(grid.into_rows(), [[1, 7, 3], [9, 5, 6]]);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let rows = vec![vec!['a', 'b'], vec!['c'], vec!['d', 'e', 'f']];
+        assert_eq!(
+            Grid::try_from_vec(rows.clone()),
+            Err(RaggedRowError {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+
+        let grid = Grid::from_vec_padded(rows.clone(), '.');
+        assert_eq!((grid.width, grid.height), (3, 3));
+        assert_eq!(grid.to_string(), "ab.\nc..\ndef");
+
+        #[allow(deprecated)]
+        let grid = Grid::from_vec(rows);
+        assert_eq!(grid.row(1), ['c', '\0', '\0']);
+        assert_eq!(Grid::from_string("ab\n\nc").to_string(), "ab\n  \nc ");
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = Grid::from_string("abc\ndef");
+        let to_string = |grid: Grid<char>| grid.to_string();
+        assert_eq!(to_string(grid.transpose()), "ad\nbe\ncf");
+        assert_eq!(to_string(grid.rotate_90()), "da\neb\nfc");
+        assert_eq!(to_string(grid.rotate_180()), "fed\ncba");
+        assert_eq!(to_string(grid.rotate_270()), "cf\nbe\nad");
+        assert_eq!(to_string(grid.flip_horizontal()), "cba\nfed");
+        assert_eq!(to_string(grid.flip_vertical()), "def\nabc");
+        assert_eq!(grid.rotate_90().rotate_270(), grid);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_string("abc\ndef");
+        let corner: Vec<Point> = grid.neighbors8(&Point::new(0, 0)).collect();
+        assert_eq!(
+            corner,
+            [Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+        let values: String = grid
+            .neighbor_cells4(&Point::new(1, 1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(values, "bdf");
+    }
 }
@@ -0,0 +1,276 @@
+//! Cellular automata over a [`Grid`].
+//!
+//! A rule maps a cell and its [`Neighbours`] to the cell's next value. Every
+//! generation applies the rule to all cells at once, so the order in which
+//! cells are visited never matters. In [`Update::WorkList`] mode only cells
+//! next to a change from the previous generation are re-evaluated, which gives
+//! the same result far faster when activity dies down.
+use super::regions::Connectivity;
+use super::Grid;
+use crate::utils::Point;
+use std::collections::hash_map::{DefaultHasher, Entry};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Which cells are re-evaluated each generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// Every cell, every generation
+    Synchronous,
+    /// Only cells that changed last generation and their neighbours
+    WorkList,
+}
+
+/// How a [`Automaton::run`] ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Generation `generation` changed nothing
+    FixedPoint { generation: usize },
+    /// The state after generation `start + period` equals the state after generation `start`
+    Cycle { start: usize, period: usize },
+    /// Gave up after the generation limit
+    Limit,
+}
+
+/// Values of the in-bounds neighbours of a cell
+pub struct Neighbours<'a, T> {
+    cells: [Option<&'a T>; 8],
+}
+
+impl<'a, T> Neighbours<'a, T> {
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.cells.iter().flatten().copied()
+    }
+
+    /// Number of neighbours equal to `value`
+    pub fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.iter().filter(|&cell| cell == value).count()
+    }
+
+    /// Number of neighbours matching a predicate
+    pub fn count_where(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.iter().filter(|&cell| predicate(cell)).count()
+    }
+}
+
+pub struct Automaton<T, F> {
+    grid: Grid<T>,
+    connectivity: Connectivity,
+    update: Update,
+    rule: F,
+    /// Cells to re-evaluate next generation in work-list mode, `None` for all
+    pending: Option<Vec<Point>>,
+    /// Number of cells changed by each generation so far
+    changes: Vec<usize>,
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + PartialEq,
+    F: Fn(&T, &Neighbours<T>) -> T,
+{
+    /// Automaton with synchronous updates
+    pub fn new(grid: Grid<T>, connectivity: Connectivity, rule: F) -> Self {
+        Self {
+            grid,
+            connectivity,
+            update: Update::Synchronous,
+            rule,
+            pending: None,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Switch to a different update strategy
+    pub fn with_update(mut self, update: Update) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Current state
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Number of generations run so far
+    pub fn generation(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Number of cells changed by each generation so far
+    pub fn changes(&self) -> &[usize] {
+        &self.changes
+    }
+
+    /// Advance one generation, returning how many cells changed
+    pub fn step(&mut self) -> usize {
+        let candidates = match (self.update, self.pending.take()) {
+            (Update::WorkList, Some(pending)) => pending,
+            _ => self.grid.points().collect(),
+        };
+
+        let updates: Vec<(Point, T)> = candidates
+            .into_iter()
+            .filter_map(|point| {
+                let cell = &self.grid[point];
+                let next = (self.rule)(cell, &self.neighbours(point));
+                (next != *cell).then_some((point, next))
+            })
+            .collect();
+
+        if self.update == Update::WorkList {
+            let mut queued = Grid::new(self.grid.width, self.grid.height, false);
+            let mut pending = Vec::new();
+            for (point, _) in &updates {
+                let around = point.neighbors8().into_iter().chain([*point]);
+                for next in around {
+                    if queued.get(&next) == Some(&false) {
+                        queued[next] = true;
+                        pending.push(next);
+                    }
+                }
+            }
+            self.pending = Some(pending);
+        }
+
+        let changed = updates.len();
+        for (point, value) in updates {
+            self.grid[point] = value;
+        }
+        self.changes.push(changed);
+        changed
+    }
+
+    /// Step until a generation changes nothing, returning the total number of changes.
+    ///
+    /// Never returns if the automaton cycles, see [`Automaton::run`] for that.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut total = 0;
+        loop {
+            match self.step() {
+                0 => return total,
+                changed => total += changed,
+            }
+        }
+    }
+
+    fn neighbours(&self, point: Point) -> Neighbours<'_, T> {
+        let mut cells = [None; 8];
+        let around = point.neighbors8();
+        let is_cardinal = |i: usize| matches!(i, 1 | 3 | 4 | 6);
+        for (i, slot) in cells.iter_mut().enumerate() {
+            if self.connectivity == Connectivity::Eight || is_cardinal(i) {
+                *slot = self.grid.get(&around[i]);
+            }
+        }
+        Neighbours { cells }
+    }
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + Eq + Hash,
+    F: Fn(&T, &Neighbours<T>) -> T,
+{
+    /// Step until a fixed point or a repeated state, giving up after `max_generations`.
+    ///
+    /// Keeps a copy of every state seen, so mind the memory on large grids.
+    pub fn run(&mut self, max_generations: usize) -> Outcome {
+        let mut seen: HashMap<u64, Vec<(usize, Grid<T>)>> = HashMap::new();
+        remember(&mut seen, &self.grid, self.generation());
+
+        for _ in 0..max_generations {
+            if self.step() == 0 {
+                return Outcome::FixedPoint {
+                    generation: self.generation(),
+                };
+            }
+            if let Some(start) = remember(&mut seen, &self.grid, self.generation()) {
+                return Outcome::Cycle {
+                    start,
+                    period: self.generation() - start,
+                };
+            }
+        }
+        Outcome::Limit
+    }
+}
+
+/// Record a state, returning the generation it was first seen at if it is a repeat
+fn remember<T: Clone + Eq + Hash>(
+    seen: &mut HashMap<u64, Vec<(usize, Grid<T>)>>,
+    grid: &Grid<T>,
+    generation: usize,
+) -> Option<usize> {
+    let mut hasher = DefaultHasher::new();
+    grid.cells.hash(&mut hasher);
+    let states = match seen.entry(hasher.finish()) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(Vec::new()),
+    };
+    if let Some((first, _)) = states.iter().find(|(_, state)| state == grid) {
+        return Some(*first);
+    }
+    states.push((generation, grid.clone()));
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Conway's game of life
+    fn life(cell: &char, neighbours: &Neighbours<char>) -> char {
+        match (cell, neighbours.count(&'#')) {
+            ('#', 2 | 3) | ('.', 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn test_cycle() {
+        let blinker = Grid::from_string(".....\n..#..\n..#..\n..#..\n.....");
+        let mut automaton = Automaton::new(blinker.clone(), Connectivity::Eight, life);
+        assert_eq!(
+            automaton.run(10),
+            Outcome::Cycle {
+                start: 0,
+                period: 2
+            }
+        );
+        assert_eq!(automaton.changes(), [4, 4]);
+        assert_eq!(automaton.grid(), &blinker);
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let grid = Grid::from_string("....\n.##.\n.#..\n....");
+        for update in [Update::Synchronous, Update::WorkList] {
+            let mut automaton =
+                Automaton::new(grid.clone(), Connectivity::Eight, life).with_update(update);
+            assert_eq!(automaton.run(10), Outcome::FixedPoint { generation: 2 });
+            assert_eq!(automaton.grid().to_string(), "....\n.##.\n.##.\n....");
+        }
+    }
+
+    #[test]
+    fn test_work_list_matches_synchronous() {
+        let grid = Grid::from_string(
+            "..........\n...#......\n....#.....\n..###.....\n..........\n..........",
+        );
+        let mut synchronous = Automaton::new(grid.clone(), Connectivity::Eight, life);
+        let mut work_list =
+            Automaton::new(grid, Connectivity::Eight, life).with_update(Update::WorkList);
+        for _ in 0..8 {
+            assert_eq!(synchronous.step(), work_list.step());
+            assert_eq!(synchronous.grid(), work_list.grid());
+        }
+    }
+}
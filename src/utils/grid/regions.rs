@@ -0,0 +1,198 @@
+//! Flood fill and connected-component labelling.
+//!
+//! Two neighbouring cells belong to the same region when the caller's `same`
+//! predicate holds for their values, e.g. `|a, b| a == b` for plots of one crop.
+use super::Grid;
+use crate::utils::{Direction, Point};
+
+/// Which neighbours count as connected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right
+    Four,
+    /// Diagonals as well
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => &Direction::CARDINAL,
+            Connectivity::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// Every cell of the grid labelled with the index of its region
+#[derive(Debug, Clone)]
+pub struct Regions {
+    /// Index into `components` for every cell
+    pub labels: Grid<usize>,
+    pub components: Vec<Component>,
+}
+
+/// Measurements of a single connected region
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// First cell of the region in row-major order
+    pub seed: Point,
+    /// Number of cells
+    pub area: usize,
+    /// Number of cell edges bordering another region or the outside of the grid
+    pub perimeter: usize,
+    /// Number of straight fence sides, which equals the number of corners
+    pub sides: usize,
+    /// Top-left corner of the bounding box
+    pub min: Point,
+    /// Bottom-right corner of the bounding box (inclusive)
+    pub max: Point,
+}
+
+/// Points of the region containing `seed`, in the order they were reached
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    seed: Point,
+    connectivity: Connectivity,
+    same: impl Fn(&T, &T) -> bool,
+) -> Vec<Point> {
+    let mut visited = Grid::new(grid.width, grid.height, false);
+    let mut region = Vec::new();
+    if grid.contains(&seed) {
+        visited[seed] = true;
+        region.push(seed);
+    }
+
+    let mut next = 0;
+    while let Some(&point) = region.get(next) {
+        next += 1;
+        for &direction in connectivity.directions() {
+            let neighbor = point.step(direction);
+            if visited.get(&neighbor) == Some(&false) && same(&grid[point], &grid[neighbor]) {
+                visited[neighbor] = true;
+                region.push(neighbor);
+            }
+        }
+    }
+    region
+}
+
+/// Split the whole grid into connected regions and measure each of them
+pub fn label<T>(
+    grid: &Grid<T>,
+    connectivity: Connectivity,
+    same: impl Fn(&T, &T) -> bool,
+) -> Regions {
+    const UNLABELLED: usize = usize::MAX;
+    let mut labels = Grid::new(grid.width, grid.height, UNLABELLED);
+    let mut components = Vec::new();
+    let mut stack = Vec::new();
+
+    for seed in grid.points() {
+        if labels[seed] != UNLABELLED {
+            continue;
+        }
+        let id = components.len();
+        let mut component = Component {
+            seed,
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+            min: seed,
+            max: seed,
+        };
+
+        labels[seed] = id;
+        stack.push(seed);
+        while let Some(point) = stack.pop() {
+            component.area += 1;
+            component.min = Point::new(component.min.x.min(point.x), component.min.y.min(point.y));
+            component.max = Point::new(component.max.x.max(point.x), component.max.y.max(point.y));
+            for &direction in connectivity.directions() {
+                let neighbor = point.step(direction);
+                if labels.get(&neighbor) == Some(&UNLABELLED) && same(&grid[point], &grid[neighbor])
+                {
+                    labels[neighbor] = id;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    // Edges and corners only depend on the finished labels
+    for (point, &id) in labels.cells_with_points() {
+        let inside = |direction: Direction| labels.get(&point.step(direction)) == Some(&id);
+        let component = &mut components[id];
+        for direction in Direction::CARDINAL {
+            let (ahead, right) = (inside(direction), inside(direction.turn_right()));
+            if !ahead {
+                component.perimeter += 1;
+            }
+            // Outer corner, or inner corner where the region wraps around a missing diagonal
+            if ahead == right && !(ahead && inside(direction.turn_right45())) {
+                component.sides += 1;
+            }
+        }
+    }
+
+    Regions { labels, components }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = Grid::from_string("aab\nabb\nbba");
+        let same = |a: &char, b: &char| a == b;
+        assert_eq!(
+            flood_fill(&grid, Point::new(0, 0), Connectivity::Four, same).len(),
+            3
+        );
+        assert_eq!(
+            flood_fill(&grid, Point::new(2, 0), Connectivity::Four, same).len(),
+            5
+        );
+        let diagonal = Grid::from_string("ab\nba");
+        let a = flood_fill(&diagonal, Point::new(0, 0), Connectivity::Eight, same);
+        assert_eq!(a, [Point::new(0, 0), Point::new(1, 1)]);
+        assert!(flood_fill(&grid, Point::new(3, 0), Connectivity::Four, same).is_empty());
+    }
+
+    #[test]
+    fn test_label() {
+        let grid = Grid::from_string("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = label(&grid, Connectivity::Four, |a, b| a == b);
+        assert_eq!(regions.components.len(), 5);
+        assert_eq!(
+            regions.labels[Point::new(3, 2)],
+            regions.labels[Point::new(2, 1)]
+        );
+
+        let measure = |c: &Component| (grid[c.seed], c.area, c.perimeter, c.sides);
+        let measured: Vec<_> = regions.components.iter().map(measure).collect();
+        assert_eq!(
+            measured,
+            [
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+
+        let c = &regions.components[2];
+        assert_eq!((c.min, c.max), (Point::new(2, 1), Point::new(3, 3)));
+    }
+
+    #[test]
+    fn test_sides_with_hole() {
+        // An 'O' region with a hole of 'X' has 8 sides
+        let grid = Grid::from_string("OOO\nOXO\nOOO");
+        let regions = label(&grid, Connectivity::Four, |a, b| a == b);
+        let outer = &regions.components[0];
+        assert_eq!((outer.area, outer.perimeter, outer.sides), (8, 16, 8));
+    }
+}
@@ -0,0 +1,232 @@
+//! Shortest paths over the cells of a [`Grid`], moving in the 4 cardinal directions.
+//!
+//! [`bfs`] and [`dijkstra`] explore the whole reachable grid and return a
+//! [`Search`] holding distances, parents for path reconstruction and the number
+//! of distinct shortest paths to every cell. [`astar`] stops at a single goal.
+use super::Grid;
+use crate::utils::{Direction, Point};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// Result of searching from a start point
+#[derive(Debug, Clone)]
+pub struct Search {
+    distances: Grid<Option<u64>>,
+    parents: Grid<Option<Point>>,
+    counts: Grid<u64>,
+}
+
+/// A path through the grid and its total cost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub cost: u64,
+    /// Points from start to goal, both included
+    pub points: Vec<Point>,
+}
+
+impl Search {
+    fn new<T>(grid: &Grid<T>) -> Self {
+        Self {
+            distances: Grid::new(grid.width, grid.height, None),
+            parents: Grid::new(grid.width, grid.height, None),
+            counts: Grid::new(grid.width, grid.height, 0),
+        }
+    }
+
+    /// Distance map, `None` for unreachable cells
+    pub fn distances(&self) -> &Grid<Option<u64>> {
+        &self.distances
+    }
+
+    /// Shortest distance to a point, if reachable
+    pub fn distance(&self, point: &Point) -> Option<u64> {
+        self.distances.get(point).copied().flatten()
+    }
+
+    /// One shortest path from the start to a point, if reachable
+    pub fn path(&self, point: &Point) -> Option<Path> {
+        let cost = self.distance(point)?;
+        let mut points = vec![*point];
+        while let Some(parent) = self.parents[*points.last().unwrap()] {
+            points.push(parent);
+        }
+        points.reverse();
+        Some(Path { cost, points })
+    }
+
+    /// Number of distinct shortest paths from the start to a point (saturating).
+    ///
+    /// Only meaningful when every step has a positive cost.
+    pub fn count_paths(&self, point: &Point) -> u64 {
+        self.counts.get(point).copied().unwrap_or(0)
+    }
+
+    /// Record reaching `to` from `from` with total cost `distance`,
+    /// returning true if it improved on the best known distance
+    fn relax(&mut self, from: Point, to: Point, distance: u64) -> bool {
+        let paths = self.counts[from];
+        match self.distances[to] {
+            Some(best) if best < distance => false,
+            Some(best) if best == distance => {
+                self.counts[to] = self.counts[to].saturating_add(paths);
+                false
+            }
+            _ => {
+                self.distances[to] = Some(distance);
+                self.parents[to] = Some(from);
+                self.counts[to] = paths;
+                true
+            }
+        }
+    }
+}
+
+/// Breadth-first search from `start`, stepping only onto cells where `passable` holds
+pub fn bfs<T>(grid: &Grid<T>, start: Point, passable: impl Fn(Point, &T) -> bool) -> Search {
+    let mut search = Search::new(grid);
+    if !grid.contains(&start) {
+        return search;
+    }
+    search.distances[start] = Some(0);
+    search.counts[start] = 1;
+
+    let mut queue = VecDeque::from([start]);
+    while let Some(point) = queue.pop_front() {
+        let distance = search.distances[point].unwrap() + 1;
+        for (next, cell) in grid.neighbor_cells4(&point) {
+            if passable(next, cell) && search.relax(point, next, distance) {
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm from `start`.
+///
+/// `cost(from, to, cell)` gives the cost of stepping from `from` onto `to`,
+/// or `None` if the step isn't allowed.
+pub fn dijkstra<T>(
+    grid: &Grid<T>,
+    start: Point,
+    cost: impl Fn(Point, Point, &T) -> Option<u64>,
+) -> Search {
+    explore(grid, start, None, cost, |_| 0)
+}
+
+/// A* search from `start` to `goal`.
+///
+/// `heuristic` must never overestimate the remaining cost to `goal`
+/// (e.g. [`Point::manhattan_distance`] when every step costs at least 1).
+/// It needn't be consistent: a cell is expanded again whenever a cheaper way
+/// to it turns up after it was first expanded.
+pub fn astar<T>(
+    grid: &Grid<T>,
+    start: Point,
+    goal: Point,
+    cost: impl Fn(Point, Point, &T) -> Option<u64>,
+    heuristic: impl Fn(Point) -> u64,
+) -> Option<Path> {
+    explore(grid, start, Some(goal), cost, heuristic).path(&goal)
+}
+
+/// Best-first search shared by [`dijkstra`] and [`astar`], stopping early once `goal` is popped
+fn explore<T>(
+    grid: &Grid<T>,
+    start: Point,
+    goal: Option<Point>,
+    cost: impl Fn(Point, Point, &T) -> Option<u64>,
+    heuristic: impl Fn(Point) -> u64,
+) -> Search {
+    let mut search = Search::new(grid);
+    if !grid.contains(&start) {
+        return search;
+    }
+    search.distances[start] = Some(0);
+    search.counts[start] = 1;
+
+    let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0, start.y, start.x))]);
+    while let Some(Reverse((_, distance, y, x))) = heap.pop() {
+        let point = Point::new(x, y);
+        // Stale entry, the point was reached more cheaply since it was pushed
+        if search.distances[point] != Some(distance) {
+            continue;
+        }
+        if Some(point) == goal {
+            break;
+        }
+
+        for direction in Direction::CARDINAL {
+            let next = point.step(direction);
+            let Some(step) = grid.get(&next).and_then(|cell| cost(point, next, cell)) else {
+                continue;
+            };
+            let total = distance + step;
+            if search.relax(point, next, total) {
+                heap.push(Reverse((total + heuristic(next), total, next.y, next.x)));
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S..#
+.#..
+...E";
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::from_string(MAZE);
+        let search = bfs(&grid, Point::new(0, 0), |_, &c| c != '#');
+        let end = Point::new(3, 2);
+        assert_eq!(search.distance(&end), Some(5));
+        assert_eq!(search.distance(&Point::new(3, 0)), None);
+        assert_eq!(search.count_paths(&end), 3);
+
+        let path = search.path(&end).unwrap();
+        assert_eq!(path.points.len(), 6);
+        assert_eq!(path.points.first(), Some(&Point::new(0, 0)));
+        assert!(path
+            .points
+            .windows(2)
+            .all(|w| w[0].manhattan_distance(&w[1]) == 1));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let grid = Grid::try_from_vec(vec![vec![1, 9, 1], vec![1, 9, 1], vec![1, 1, 1]]).unwrap();
+        let cost = |_, _, &weight: &u64| Some(weight);
+        let (start, goal) = (Point::new(0, 0), Point::new(2, 0));
+
+        let search = dijkstra(&grid, start, cost);
+        assert_eq!(search.distance(&goal), Some(6));
+        assert_eq!(search.count_paths(&goal), 1);
+
+        let path = astar(&grid, start, goal, cost, |p| {
+            p.manhattan_distance(&goal) as u64
+        });
+        assert_eq!(path, search.path(&goal));
+        assert_eq!(path.unwrap().points.len(), 7);
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        let grid = Grid::try_from_vec(vec![vec![5, 5, 3], vec![2, 1, 1]]).unwrap();
+        // Never overestimates, but drops by more than a step's cost from (1, 0) to (2, 0)
+        let estimate = Grid::try_from_vec(vec![vec![3, 1, 0], vec![5, 0, 2]]).unwrap();
+        let cost = |_, _, &weight: &u64| Some(weight);
+        let (start, goal) = (Point::new(0, 0), Point::new(2, 0));
+
+        let path = astar(&grid, start, goal, cost, |p| estimate[p]).unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(
+            Some(path.cost),
+            dijkstra(&grid, start, cost).distance(&goal)
+        );
+    }
+}
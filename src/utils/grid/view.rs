@@ -0,0 +1,199 @@
+use super::Grid;
+use crate::utils::Point;
+use std::ops::Index;
+
+/// Borrowed rectangular window into a [`Grid`], addressed with coordinates local to the window
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+// Derived impls would needlessly require `T: Clone`
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    /// View of the whole grid
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Self {
+            grid,
+            x: 0,
+            y: 0,
+            width: grid.width,
+            height: grid.height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Position of the view's top-left corner in the underlying grid
+    pub fn origin(&self) -> Point {
+        Point::new(self.x as i32, self.y as i32)
+    }
+
+    /// Narrower view at `(x, y)` relative to this one (returns None if it doesn't fit)
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> Option<Self> {
+        (x + width <= self.width && y + height <= self.height).then_some(Self {
+            grid: self.grid,
+            x: self.x + x,
+            y: self.y + y,
+            width,
+            height,
+        })
+    }
+
+    /// Check if a point is within the view
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    /// Get a value at a point (returns None if outside the view)
+    pub fn get(&self, point: &Point) -> Option<&'a T> {
+        if !self.contains(point) {
+            return None;
+        }
+        Some(&self.row(point.y as usize)[point.x as usize])
+    }
+
+    /// Cells of row `y` within the view (panics if out of bounds)
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.grid.row(self.y + y)[self.x..self.x + self.width]
+    }
+
+    /// Iterate over the rows from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Iterate over all cells of column `x` from top to bottom (panics if out of bounds)
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &'a T> + '_ {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.rows().map(move |row| &row[x])
+    }
+
+    /// Iterate over all points in the view
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Point::new(x as i32, y as i32)))
+    }
+
+    /// Iterate over all cells with their positions
+    pub fn cells_with_points(&self) -> impl Iterator<Item = (Point, &'a T)> + '_ {
+        self.points().zip(self.rows().flatten())
+    }
+
+    /// Split into blocks separated by columns where every cell is blank.
+    ///
+    /// Blank columns themselves are dropped, as are empty blocks.
+    pub fn split_columns(&self, is_blank: impl Fn(&T) -> bool) -> Vec<Self> {
+        let blank: Vec<bool> = (0..self.width)
+            .map(|x| self.column(x).all(&is_blank))
+            .collect();
+        spans(&blank)
+            .map(|(start, end)| self.view(start, 0, end - start, self.height).unwrap())
+            .collect()
+    }
+
+    /// Split into blocks separated by rows where every cell is blank.
+    ///
+    /// Blank rows themselves are dropped, as are empty blocks.
+    pub fn split_rows(&self, is_blank: impl Fn(&T) -> bool) -> Vec<Self> {
+        let blank: Vec<bool> = self.rows().map(|row| row.iter().all(&is_blank)).collect();
+        spans(&blank)
+            .map(|(start, end)| self.view(0, start, self.width, end - start).unwrap())
+            .collect()
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copy the viewed cells into a grid of their own
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            cells: self.rows().flatten().cloned().collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Point> for GridView<'_, T> {
+    type Output = T;
+
+    /// Value at a point (panics if outside the view)
+    fn index(&self, point: Point) -> &T {
+        match self.get(&point) {
+            Some(cell) => cell,
+            None => panic!(
+                "{point:?} out of bounds for a {}x{} view",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Half-open ranges of consecutive non-blank indices
+fn spans(blank: &[bool]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut start = 0;
+    (0..=blank.len()).filter_map(move |i| {
+        if i < blank.len() && !blank[i] {
+            return None;
+        }
+        let span = (start < i).then_some((start, i));
+        start = i + 1;
+        span
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let grid = Grid::from_string("abcd\nefgh\nijkl");
+        let view = grid.view(1, 1, 2, 2).unwrap();
+        assert_eq!(view.origin(), Point::new(1, 1));
+        assert_eq!(view.row(1), &['j', 'k']);
+        assert_eq!(view[Point::new(1, 0)], 'g');
+        assert_eq!(view.get(&Point::new(2, 0)), None);
+        assert_eq!(view.to_grid().to_string(), "fg\njk");
+        assert!(grid.view(3, 0, 2, 1).is_none());
+    }
+
+    #[test]
+    fn test_split() {
+        let grid = Grid::from_string("12  3\n 4  5\n\n67 89");
+        let columns: Vec<String> = grid
+            .split_columns(|&c| c == ' ')
+            .iter()
+            .map(|block| block.to_grid().to_string())
+            .collect();
+        assert_eq!(columns, ["12\n 4\n  \n67", " 3\n 5\n  \n89"]);
+
+        let rows: Vec<(Point, usize)> = grid
+            .split_rows(|&c| c == ' ')
+            .iter()
+            .map(|block| (block.origin(), block.height()))
+            .collect();
+        assert_eq!(rows, [(Point::new(0, 0), 2), (Point::new(0, 3), 1)]);
+    }
+}
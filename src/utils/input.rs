@@ -1,5 +1,83 @@
 use super::grid::Grid;
+use std::env;
+use std::fmt;
 use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Environment variable overriding the directory that holds `yearXXXX/dayXX.txt` inputs
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a puzzle's input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Read the whole input
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Path(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut data = String::new();
+                io::stdin().read_to_string(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Overrides for where puzzle inputs are found
+#[derive(Debug, Clone, Default)]
+pub struct InputConfig {
+    /// Explicit input file, or `-` for stdin
+    pub input: Option<String>,
+    /// Directory holding `yearXXXX/dayXX.txt` inputs instead of `inputs/`
+    pub input_dir: Option<PathBuf>,
+    /// Use the example inputs in `tests/yearXXXX/dayXX_input.txt`
+    pub use_test: bool,
+}
+
+impl InputConfig {
+    /// Configuration taken from the environment only (`AOC_INPUT_DIR`)
+    pub fn from_env() -> Self {
+        Self {
+            input_dir: env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            ..Self::default()
+        }
+    }
+}
+
+/// Resolve the input for a puzzle.
+///
+/// An explicit input wins, then example inputs when testing, then the input
+/// directory override, falling back to `inputs/yearXXXX/dayXX.txt`.
+pub fn resolve_input(year: u32, day: u32, config: &InputConfig) -> InputSource {
+    match (&config.input, config.use_test, &config.input_dir) {
+        (Some(input), ..) if input == "-" => InputSource::Stdin,
+        (Some(input), ..) => InputSource::Path(PathBuf::from(input)),
+        (None, true, _) => {
+            InputSource::Path(format!("tests/year{year}/day{day:02}_input.txt").into())
+        }
+        (None, false, Some(dir)) => {
+            InputSource::Path(dir.join(format!("year{year}/day{day:02}.txt")))
+        }
+        (None, false, None) => {
+            InputSource::Path(format!("inputs/year{year}/day{day:02}.txt").into())
+        }
+    }
+}
 
 /// Read input file as a single string
 pub fn read_input(day: u8, use_test: bool) -> String {
@@ -70,4 +148,32 @@ mod tests {
         // These tests would require actual input files
         // They're here as examples of how to use the functions
     }
+
+    #[test]
+    fn test_resolve_input() {
+        let mut config = InputConfig::default();
+        let path = |p: &str| InputSource::Path(PathBuf::from(p));
+        assert_eq!(
+            resolve_input(2025, 1, &config),
+            path("inputs/year2025/day01.txt")
+        );
+
+        config.input_dir = Some(PathBuf::from("/data/alice"));
+        assert_eq!(
+            resolve_input(2025, 1, &config),
+            path("/data/alice/year2025/day01.txt")
+        );
+
+        config.use_test = true;
+        assert_eq!(
+            resolve_input(2025, 1, &config),
+            path("tests/year2025/day01_input.txt")
+        );
+
+        config.input = Some("mine.txt".to_string());
+        assert_eq!(resolve_input(2025, 1, &config), path("mine.txt"));
+
+        config.input = Some("-".to_string());
+        assert_eq!(resolve_input(2025, 1, &config), InputSource::Stdin);
+    }
 }
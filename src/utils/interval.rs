@@ -0,0 +1,263 @@
+//! Sets of integers stored as sorted, disjoint inclusive ranges.
+//!
+//! Ranges are inclusive so a set can reach the type's maximum (`0..=u64::MAX`
+//! has no exclusive form), and lengths are counted in `u128` because such a set
+//! can hold more values than the type itself can count.
+use std::ops::RangeInclusive;
+
+/// Primitive integer usable as an [`IntervalSet`] bound
+pub trait Discrete: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// The next value, None at `MAX`
+    fn succ(self) -> Option<Self>;
+
+    /// The previous value, None at `MIN`
+    fn pred(self) -> Option<Self>;
+
+    /// Number of values in `start..=end`, None only for the full range of a 128-bit type
+    fn span(start: Self, end: Self) -> Option<u128>;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty => $unsigned:ty),*) => {
+        $(impl Discrete for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn span(start: Self, end: Self) -> Option<u128> {
+                // The difference always fits in the unsigned type of the same width
+                (end.wrapping_sub(start) as $unsigned as u128).checked_add(1)
+            }
+        })*
+    };
+}
+
+impl_discrete!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+/// Set of integers, kept as sorted ranges that neither overlap nor touch
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of disjoint ranges (not the number of values, see [`covered_len`](Self::covered_len))
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Add a range, merging it with any range it overlaps or touches (empty ranges are ignored)
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // Ranges entirely before `start` that don't touch it stay, as do those entirely after `end`
+        let first = self
+            .ranges
+            .partition_point(|&(_, b)| b.succ().is_some_and(|next| next < start));
+        let last = self
+            .ranges
+            .partition_point(|&(a, _)| a.pred().is_none_or(|previous| previous <= end));
+
+        let merged = match &self.ranges[first..last] {
+            [] => (start, end),
+            overlapping => (
+                start.min(overlapping[0].0),
+                end.max(overlapping[overlapping.len() - 1].1),
+            ),
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Whether `value` is in the set, by binary search
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, b)| b < value);
+        self.ranges.get(i).is_some_and(|&(a, _)| a <= value)
+    }
+
+    /// Total number of values in the set, None if it overflows `u128`
+    /// (which only a 128-bit `T` can do)
+    pub fn covered_len(&self) -> Option<u128> {
+        self.ranges
+            .iter()
+            .try_fold(0u128, |total, &(a, b)| total.checked_add(T::span(a, b)?))
+    }
+
+    /// The ranges in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(a, b)| a..=b)
+    }
+
+    /// The values missing between consecutive ranges, in increasing order
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        // Ranges never touch, so both neighbours exist
+        self.ranges
+            .windows(2)
+            .map(|pair| pair[0].1.succ().unwrap()..=pair[1].0.pred().unwrap())
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.iter());
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while let (Some(&(a1, b1)), Some(&(a2, b2))) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (a1.max(a2), b1.min(b2));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // Drop whichever range ends first, it can't meet anything further on
+            if b1 < b2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        // Pieces of two normalised sets can't touch each other
+        Self { ranges }
+    }
+
+    /// Values in `self` but not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement(T::MIN..=T::MAX))
+    }
+
+    /// Values within `bounds` that are not in the set
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        let (low, high) = bounds.into_inner();
+        let mut ranges = Vec::new();
+        // Smallest value not yet known to be covered, None once the set reaches `T::MAX`
+        let mut next = Some(low);
+
+        for &(a, b) in &self.ranges {
+            let Some(candidate) = next else { break };
+            if b < low {
+                continue;
+            }
+            if a > high {
+                break;
+            }
+            if a > candidate {
+                ranges.push((candidate, a.pred().unwrap()));
+            }
+            next = b.succ();
+        }
+        if let Some(candidate) = next.filter(|&candidate| candidate <= high) {
+            ranges.push((candidate, high));
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<u64>) -> Vec<RangeInclusive<u64>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert_normalises() {
+        let mut set: IntervalSet<u64> = [10..=14, 3..=5, 16..=20, 12..=18, 6..=6]
+            .into_iter()
+            .collect();
+        let (start, end) = (30, 29);
+        set.insert(start..=end);
+        assert_eq!(ranges(&set), [3..=6, 10..=20]);
+        assert_eq!(set.covered_len(), Some(15));
+        assert_eq!(set.gaps().collect::<Vec<_>>(), [7..=9]);
+
+        assert!(set.contains(3) && set.contains(6) && set.contains(20));
+        assert!(!set.contains(2) && !set.contains(7) && !set.contains(21));
+        assert_eq!(IntervalSet::<u64>::new().covered_len(), Some(0));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<u64> = [0..=10, 20..=30].into_iter().collect();
+        let b: IntervalSet<u64> = [5..=25].into_iter().collect();
+
+        assert_eq!(ranges(&a.union(&b)), [0..=30]);
+        assert_eq!(ranges(&a.intersection(&b)), [5..=10, 20..=25]);
+        assert_eq!(ranges(&a.difference(&b)), [0..=4, 26..=30]);
+        assert_eq!(ranges(&b.difference(&a)), [11..=19]);
+        assert_eq!(ranges(&a.complement(5..=40)), [11..=19, 31..=40]);
+        assert_eq!(ranges(&a.complement(12..=18)), [12..=18]);
+        assert!(a.complement(0..=10).is_empty());
+    }
+
+    #[test]
+    fn test_full_range() {
+        let mut set: IntervalSet<u64> = [u64::MAX - 1..=u64::MAX, 0..=0].into_iter().collect();
+        assert!(set.contains(u64::MAX));
+        assert_eq!(set.covered_len(), Some(3));
+        assert_eq!(ranges(&set.complement(0..=u64::MAX)), [1..=u64::MAX - 2]);
+
+        set.insert(1..=u64::MAX - 2);
+        assert_eq!(ranges(&set), [0..=u64::MAX]);
+        assert_eq!(set.covered_len(), Some(1 << 64));
+        assert!(set.complement(0..=u64::MAX).is_empty());
+        assert!(set.difference(&set).is_empty());
+
+        // Signed bounds work the same way
+        let signed: IntervalSet<i8> = [-128..=-1, 1..=127].into_iter().collect();
+        assert_eq!(signed.gaps().collect::<Vec<_>>(), [0..=0]);
+        assert_eq!(signed.covered_len(), Some(255));
+        assert_eq!(signed.complement(-128..=127).covered_len(), Some(1));
+
+        // Only the full range of a 128-bit type can't be counted
+        let wide: IntervalSet<i128> = [i128::MIN..=-1, 0..=i128::MAX].into_iter().collect();
+        assert_eq!(wide.covered_len(), None);
+        let wide: IntervalSet<i128> = [i128::MIN..=i128::MAX - 1].into_iter().collect();
+        assert_eq!(wide.covered_len(), Some(u128::MAX));
+    }
+}
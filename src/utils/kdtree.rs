@@ -0,0 +1,308 @@
+//! KD-tree over 3D points for nearest-neighbour queries.
+//!
+//! Distances are squared Euclidean, so they stay exact integers.
+use super::point::Point3;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Balanced KD-tree, stored implicitly: the node for a range of `order` is its middle element
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<Point3>,
+    order: Vec<usize>,
+}
+
+fn axis(point: &Point3, depth: usize) -> i64 {
+    match depth % 3 {
+        0 => point.x,
+        1 => point.y,
+        _ => point.z,
+    }
+}
+
+impl KdTree {
+    pub fn new(points: &[Point3]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        Self {
+            points: points.to_vec(),
+            order,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Point with the given index in the slice the tree was built from
+    pub fn point(&self, index: usize) -> &Point3 {
+        &self.points[index]
+    }
+
+    /// The `k` points closest to `target` as `(index, squared distance)`, closest first.
+    ///
+    /// Ties are broken by index, so the result is always a prefix of the result for a larger `k`.
+    pub fn nearest(&self, target: &Point3, k: usize) -> Vec<(usize, i64)> {
+        self.nearest_after(target, k, None)
+    }
+
+    /// Like [`nearest`](Self::nearest), but only points after `after`, a `(squared distance, index)`
+    /// pair, in closest first order
+    fn nearest_after(
+        &self,
+        target: &Point3,
+        k: usize,
+        after: Option<(i64, usize)>,
+    ) -> Vec<(usize, i64)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(0, self.order.len(), 0, target, k, after, &mut best);
+        }
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|(distance, index)| (index, distance))
+            .collect()
+    }
+
+    /// All points within `max_squared_distance` of `target` as `(index, squared distance)`, closest first
+    pub fn within(&self, target: &Point3, max_squared_distance: i64) -> Vec<(usize, i64)> {
+        let mut found = Vec::new();
+        self.search_within(
+            0,
+            self.order.len(),
+            0,
+            target,
+            max_squared_distance,
+            &mut found,
+        );
+        found.sort_unstable_by_key(|&(index, distance)| (distance, index));
+        found
+    }
+
+    /// Every pair of points as `(i, j, squared distance)` with `i < j`, shortest first.
+    ///
+    /// Pairs are generated lazily from growing nearest-neighbour lists, so taking
+    /// the first few edges is far cheaper than sorting all n² pairs.
+    pub fn edges(&self) -> Edges<'_> {
+        let mut edges = Edges {
+            tree: self,
+            neighbours: vec![Vec::new(); self.len()],
+            next: vec![0; self.len()],
+            heap: BinaryHeap::new(),
+        };
+        for i in 0..self.len() {
+            edges.fetch(i, Edges::INITIAL_NEIGHBOURS);
+            edges.push_next(i);
+        }
+        edges
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_nearest(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        target: &Point3,
+        k: usize,
+        after: Option<(i64, usize)>,
+        best: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let index = self.order[mid];
+        let point = &self.points[index];
+
+        let candidate = (point.squared_distance(target), index);
+        // Points up to `after` were returned by an earlier query
+        if after.is_none_or(|after| candidate > after) {
+            if best.len() < k {
+                best.push(candidate);
+            } else if best.peek().is_some_and(|&worst| candidate < worst) {
+                best.pop();
+                best.push(candidate);
+            }
+        }
+
+        let diff = axis(target, depth) - axis(point, depth);
+        let (near, far) = if diff < 0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search_nearest(near.0, near.1, depth + 1, target, k, after, best);
+        // Equal distances still matter for breaking ties by index
+        if best.len() < k || best.peek().is_some_and(|&(worst, _)| diff * diff <= worst) {
+            self.search_nearest(far.0, far.1, depth + 1, target, k, after, best);
+        }
+    }
+
+    fn search_within(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        target: &Point3,
+        max_squared_distance: i64,
+        found: &mut Vec<(usize, i64)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let index = self.order[mid];
+        let point = &self.points[index];
+
+        let distance = point.squared_distance(target);
+        if distance <= max_squared_distance {
+            found.push((index, distance));
+        }
+
+        let diff = axis(target, depth) - axis(point, depth);
+        if diff <= 0 || diff * diff <= max_squared_distance {
+            self.search_within(lo, mid, depth + 1, target, max_squared_distance, found);
+        }
+        if diff >= 0 || diff * diff <= max_squared_distance {
+            self.search_within(mid + 1, hi, depth + 1, target, max_squared_distance, found);
+        }
+    }
+}
+
+/// Arrange `order` so that the middle of every range splits it on that depth's axis
+fn build(points: &[Point3], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by_key(mid, |&i| axis(&points[i], depth));
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+/// Iterator over all pairs of points in increasing distance, see [`KdTree::edges`]
+pub struct Edges<'a> {
+    tree: &'a KdTree,
+    /// Nearest neighbours fetched so far for every point, closest first
+    neighbours: Vec<Vec<(usize, i64)>>,
+    /// Position of the next unvisited neighbour of every point
+    next: Vec<usize>,
+    /// Next neighbour of every point, keyed by distance
+    heap: BinaryHeap<Reverse<(i64, usize, usize)>>,
+}
+
+impl Edges<'_> {
+    const INITIAL_NEIGHBOURS: usize = 8;
+
+    /// Fetch up to `k` more neighbours of point `i`, excluding itself, continuing
+    /// after the furthest one fetched so far
+    fn fetch(&mut self, i: usize, k: usize) {
+        let tree = self.tree;
+        let after = self.neighbours[i]
+            .last()
+            .map(|&(j, distance)| (distance, j));
+        // One extra in case `i` itself is among them
+        let mut more = tree.nearest_after(&tree.points[i], k + 1, after);
+        more.retain(|&(j, _)| j != i);
+        more.truncate(k);
+        self.neighbours[i].extend(more);
+    }
+
+    /// Queue the next unvisited neighbour of point `i`, fetching more when they run out
+    fn push_next(&mut self, i: usize) {
+        let fetched = self.neighbours[i].len();
+        if self.next[i] == fetched && fetched < self.tree.len() - 1 {
+            self.fetch(i, fetched);
+        }
+        if let Some(&(j, distance)) = self.neighbours[i].get(self.next[i]) {
+            self.heap.push(Reverse((distance, i, j)));
+        }
+    }
+}
+
+impl Iterator for Edges<'_> {
+    type Item = (usize, usize, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Reverse((distance, i, j)) = self.heap.pop()?;
+            self.next[i] += 1;
+            self.push_next(i);
+            // Every pair shows up from both ends, only report it from the lower index
+            if i < j {
+                return Some((i, j, distance));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<Point3> {
+        // Deterministic pseudo-random cloud with some duplicate coordinates
+        let mut state = 12345u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % 50) as i64 - 25
+        };
+        (0..200)
+            .map(|_| Point3::new(next(), next(), next()))
+            .collect()
+    }
+
+    fn brute_force(points: &[Point3], target: &Point3) -> Vec<(usize, i64)> {
+        let mut all: Vec<(usize, i64)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, p.squared_distance(target)))
+            .collect();
+        all.sort_unstable_by_key(|&(i, d)| (d, i));
+        all
+    }
+
+    #[test]
+    fn test_nearest_and_within() {
+        let points = points();
+        let tree = KdTree::new(&points);
+        for target in [Point3::new(0, 0, 0), Point3::new(20, -7, 3), points[17]] {
+            let expected = brute_force(&points, &target);
+            assert_eq!(tree.nearest(&target, 10), expected[..10]);
+            let (index, distance) = expected[9];
+            assert_eq!(
+                tree.nearest_after(&target, 10, Some((distance, index))),
+                expected[10..20]
+            );
+            let within: Vec<_> = expected
+                .iter()
+                .copied()
+                .filter(|&(_, d)| d <= 150)
+                .collect();
+            assert_eq!(tree.within(&target, 150), within);
+        }
+        assert!(tree.nearest(&Point3::default(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_edges() {
+        let points = points();
+        let tree = KdTree::new(&points);
+        let edges: Vec<(usize, usize, i64)> = tree.edges().collect();
+
+        let n = points.len();
+        assert_eq!(edges.len(), n * (n - 1) / 2);
+        assert!(edges.windows(2).all(|w| w[0].2 <= w[1].2));
+        assert!(edges
+            .iter()
+            .all(|&(i, j, d)| i < j && points[i].squared_distance(&points[j]) == d));
+    }
+}
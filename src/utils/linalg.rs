@@ -0,0 +1,653 @@
+//! Exact integer linear algebra and a small integer linear program solver.
+//!
+//! Everything is computed without floating point: elimination is fraction-free
+//! (Bareiss) over checked `i128`, and the LP relaxations inside [`minimise`] use exact
+//! [`Rational`] arithmetic, so results are exact rather than approximately right.
+use super::math::{checked_gcd, checked_lcm, gcd};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Exact fraction, always stored in lowest terms with a positive denominator.
+///
+/// Common factors are cancelled before multiplying, and arithmetic that still
+/// doesn't fit in `i128` panics rather than wrapping. Comparisons never overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// `num / den` (panics if `den` is zero)
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let g = gcd(num, den);
+        let sign = den.signum();
+        Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn floor(&self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    pub fn ceil(&self) -> i128 {
+        -(-self.num).div_euclid(self.den)
+    }
+
+    /// `1 / self` (panics if `self` is zero)
+    pub fn recip(&self) -> Self {
+        Self::new(self.den, self.num)
+    }
+}
+
+/// Unwrap the result of checked `i128` arithmetic on fractions
+fn exact(value: Option<i128>) -> i128 {
+    value.expect("rational arithmetic overflowed i128")
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Self { num: n, den: 1 }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self::from(n as i128)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        if self.den == 1 && other.den == 1 {
+            return Rational::from(exact(self.num.checked_add(other.num)));
+        }
+        // Over the least common denominator
+        let g = gcd(self.den, other.den);
+        let (left, right) = (other.den / g, self.den / g);
+        let num =
+            exact(self.num.checked_mul(left)).checked_add(exact(other.num.checked_mul(right)));
+        Rational::new(exact(num), exact(self.den.checked_mul(left)))
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        if self.den == 1 && other.den == 1 {
+            return Rational::from(exact(self.num.checked_mul(other.num)));
+        }
+        // Both are in lowest terms, so only factors across the two can cancel
+        let (g1, g2) = (gcd(self.num, other.den), gcd(other.num, self.den));
+        Rational {
+            num: exact((self.num / g1).checked_mul(other.num / g2)),
+            den: exact((self.den / g2).checked_mul(other.den / g1)),
+        }
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        self.mul(other.recip())
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: exact(self.num.checked_neg()),
+            den: self.den,
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let (Some(left), Some(right)) = (
+            self.num.checked_mul(other.den),
+            other.num.checked_mul(self.den),
+        ) {
+            return left.cmp(&right);
+        }
+        // Cross-multiplying overflows, so compare continued fraction terms instead
+        let mut order = self.floor().cmp(&other.floor());
+        let mut a = (self.num.rem_euclid(self.den), self.den);
+        let mut b = (other.num.rem_euclid(other.den), other.den);
+        let mut flipped = false;
+        // Same integer part, so compare the fractional parts `a.0 / a.1` and `b.0 / b.1`
+        while order == Ordering::Equal {
+            if a.0 == 0 || b.0 == 0 {
+                order = a.0.cmp(&b.0);
+                break;
+            }
+            // The larger fraction has the smaller reciprocal
+            (a, b) = ((a.1, a.0), (b.1, b.0));
+            flipped = !flipped;
+            order = (a.0 / a.1).cmp(&(b.0 / b.1));
+            (a.0, b.0) = (a.0 % a.1, b.0 % b.1);
+        }
+        if flipped {
+            order.reverse()
+        } else {
+            order
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Row echelon form of a matrix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Echelon {
+    /// The eliminated matrix, with rows below `pivots.len()` all zero
+    pub rows: Vec<Vec<i128>>,
+    /// Pivot column of each non-zero row
+    pub pivots: Vec<usize>,
+    /// Number of row swaps performed, for the sign of the determinant
+    pub swaps: usize,
+}
+
+impl Echelon {
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+}
+
+/// Bring a matrix to row echelon form with Bareiss' fraction-free elimination.
+///
+/// Every division is exact, so entries stay integers and grow only as fast as
+/// the matrix's minors. Returns None if an intermediate value overflows `i128`.
+pub fn bareiss(matrix: &[Vec<i64>]) -> Option<Echelon> {
+    let mut rows: Vec<Vec<i128>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|&x| x as i128).collect())
+        .collect();
+    let width = rows.first().map_or(0, Vec::len);
+    let mut pivots = Vec::new();
+    let mut swaps = 0;
+    let mut previous = 1;
+
+    for col in 0..width {
+        let r = pivots.len();
+        let Some(found) = (r..rows.len()).find(|&i| rows[i][col] != 0) else {
+            continue;
+        };
+        if found != r {
+            rows.swap(found, r);
+            swaps += 1;
+        }
+
+        let pivot = rows[r][col];
+        let (top, below) = rows.split_at_mut(r + 1);
+        for row in below {
+            // Columns left of `col` are already zero in the rows below
+            let factor = row[col];
+            for (v, &p) in row[col..].iter_mut().zip(&top[r][col..]) {
+                *v = v.checked_mul(pivot)?.checked_sub(p.checked_mul(factor)?)? / previous;
+            }
+        }
+        previous = pivot;
+        pivots.push(col);
+    }
+
+    Some(Echelon {
+        rows,
+        pivots,
+        swaps,
+    })
+}
+
+/// Rank of a matrix, None if eliminating it overflows `i128`
+pub fn rank(matrix: &[Vec<i64>]) -> Option<usize> {
+    Some(bareiss(matrix)?.rank())
+}
+
+/// Determinant of a square matrix (panics if it isn't square), None if it overflows `i128`
+pub fn determinant(matrix: &[Vec<i64>]) -> Option<i128> {
+    let n = matrix.len();
+    assert!(
+        matrix.iter().all(|row| row.len() == n),
+        "determinant of a non-square matrix"
+    );
+    if n == 0 {
+        return Some(1);
+    }
+    let echelon = bareiss(matrix)?;
+    if echelon.rank() < n {
+        return Some(0);
+    }
+    // The last pivot of a Bareiss elimination is the determinant
+    let det = echelon.rows[n - 1][n - 1];
+    if echelon.swaps.is_multiple_of(2) {
+        Some(det)
+    } else {
+        det.checked_neg()
+    }
+}
+
+/// Basis of the integer vectors `x` with `matrix * x == 0`, one vector per free column.
+///
+/// Each basis vector is scaled to the smallest integers. Returns None if the
+/// elimination overflows `i128` or a basis vector doesn't fit in `i64`.
+pub fn nullspace(matrix: &[Vec<i64>]) -> Option<Vec<Vec<i64>>> {
+    let echelon = bareiss(matrix)?;
+    let width = matrix.first().map_or(0, Vec::len);
+    let is_pivot: Vec<bool> = (0..width).map(|c| echelon.pivots.contains(&c)).collect();
+
+    (0..width)
+        .filter(|&free| !is_pivot[free])
+        .map(|free| {
+            let mut x = vec![Rational::ZERO; width];
+            x[free] = Rational::ONE;
+            // Back-substitute the pivot variables, bottom row first
+            for (r, &p) in echelon.pivots.iter().enumerate().rev() {
+                let row = &echelon.rows[r];
+                let rest = (p + 1..width)
+                    .fold(Rational::ZERO, |acc, j| acc + Rational::from(row[j]) * x[j]);
+                x[p] = -rest / Rational::from(row[p]);
+            }
+            to_integers(&x)
+        })
+        .collect()
+}
+
+/// Scale a rational vector to the smallest integer vector pointing the same way,
+/// None if that doesn't fit in `i64`
+fn to_integers(x: &[Rational]) -> Option<Vec<i64>> {
+    let scale = x
+        .iter()
+        .try_fold(1i128, |acc, q| checked_lcm(acc, q.denominator()))?;
+    let scaled: Vec<i128> = x
+        .iter()
+        .map(|q| q.numerator().checked_mul(scale / q.denominator()))
+        .collect::<Option<_>>()?;
+    let g = scaled
+        .iter()
+        .try_fold(0, |acc, &v| checked_gcd(acc, v))?
+        .max(1);
+    scaled.iter().map(|&v| i64::try_from(v / g).ok()).collect()
+}
+
+/// Minimise `sum(x)` subject to `a * x == b` with `x` non-negative integers, see [`minimise`]
+pub fn minimise_sum(a: &[Vec<i64>], b: &[i64]) -> Option<Vec<i64>> {
+    let width = a.first().map_or(0, Vec::len);
+    minimise(&vec![1; width], a, b)
+}
+
+/// Minimise `costs · x` subject to `a * x == b` with `x` non-negative integers.
+///
+/// Branch and bound over exact LP relaxations, so the returned solution is
+/// optimal (ties broken arbitrarily), and None means no integer solution exists.
+/// Costs must be non-negative (panics otherwise), so the objective is bounded
+/// below. The feasible region must be bounded for this to terminate, which
+/// holds for instance when `a` is non-negative and has no all-zero column.
+pub fn minimise(costs: &[i64], a: &[Vec<i64>], b: &[i64]) -> Option<Vec<i64>> {
+    assert_eq!(a.len(), b.len(), "one right-hand side per row");
+    let width = costs.len();
+    assert!(
+        a.iter().all(|row| row.len() == width),
+        "one cost per column"
+    );
+    assert!(costs.iter().all(|&c| c >= 0), "costs must be non-negative");
+
+    let problem = Problem { costs, a, b };
+    let mut bounds = vec![(0, None); width];
+    let mut best = None;
+    problem.branch(&mut bounds, &mut best);
+    best.map(|(_, x)| x)
+}
+
+struct Problem<'a> {
+    costs: &'a [i64],
+    a: &'a [Vec<i64>],
+    b: &'a [i64],
+}
+
+/// Inclusive lower and optional upper bound of a variable
+type Bound = (i64, Option<i64>);
+
+impl Problem<'_> {
+    fn branch(&self, bounds: &mut Vec<Bound>, best: &mut Option<(i128, Vec<i64>)>) {
+        let Some((objective, x)) = self.relaxation(bounds) else {
+            return;
+        };
+        // Integer costs make every integer objective an integer
+        if best
+            .as_ref()
+            .is_some_and(|(incumbent, _)| objective.ceil() >= *incumbent)
+        {
+            return;
+        }
+
+        let Some(j) = x.iter().position(|v| !v.is_integer()) else {
+            let x = x.iter().map(|v| v.numerator() as i64).collect();
+            *best = Some((objective.numerator(), x));
+            return;
+        };
+
+        let saved = bounds[j];
+        let (down, up) = (x[j].floor() as i64, x[j].ceil() as i64);
+        bounds[j] = (saved.0, Some(down));
+        self.branch(bounds, best);
+        bounds[j] = (up, saved.1);
+        self.branch(bounds, best);
+        bounds[j] = saved;
+    }
+
+    /// Optimum of the LP relaxation within `bounds`, if feasible
+    fn relaxation(&self, bounds: &[Bound]) -> Option<(Rational, Vec<Rational>)> {
+        let n = self.costs.len();
+
+        // Each active bound becomes an equality row `x_j ± slack = bound` with its own slack
+        let limits: Vec<(usize, i64, i64)> = bounds
+            .iter()
+            .enumerate()
+            .flat_map(|(j, &(lower, upper))| {
+                let lower = (lower > 0).then_some((j, -1, lower));
+                lower.into_iter().chain(upper.map(|upper| (j, 1, upper)))
+            })
+            .collect();
+
+        let m = self.a.len() + limits.len();
+        let real = n + limits.len();
+        let columns = real + m;
+        let mut tableau = Tableau {
+            rows: Vec::with_capacity(m),
+            basis: (real..columns).collect(),
+        };
+        let original = self.a.iter().zip(self.b).map(|(coefficients, &rhs)| {
+            let mut row = vec![Rational::ZERO; columns + 1];
+            for (v, &c) in row.iter_mut().zip(coefficients) {
+                *v = Rational::from(c);
+            }
+            (row, rhs)
+        });
+        let limited = limits.iter().enumerate().map(|(s, &(j, sign, bound))| {
+            let mut row = vec![Rational::ZERO; columns + 1];
+            row[j] = Rational::ONE;
+            row[n + s] = Rational::from(sign);
+            (row, bound)
+        });
+        for (i, (mut row, rhs)) in original.chain(limited).enumerate() {
+            row[columns] = Rational::from(rhs);
+            // Artificial variables need a non-negative right-hand side
+            if rhs < 0 {
+                row.iter_mut().for_each(|v| *v = -*v);
+            }
+            row[real + i] = Rational::ONE;
+            tableau.rows.push(row);
+        }
+
+        // Phase one: drive the artificial variables to zero
+        let phase_one: Vec<Rational> = (0..columns)
+            .map(|j| {
+                if j >= real {
+                    Rational::ONE
+                } else {
+                    Rational::ZERO
+                }
+            })
+            .collect();
+        tableau.optimise(&phase_one, columns);
+        if tableau.objective(&phase_one) != Rational::ZERO {
+            return None;
+        }
+        tableau.evict(real);
+
+        // Phase two: the real objective, never letting artificial variables back in
+        let phase_two: Vec<Rational> = (0..columns)
+            .map(|j| match j < n {
+                true => Rational::from(self.costs[j]),
+                false => Rational::ZERO,
+            })
+            .collect();
+        tableau.optimise(&phase_two, real);
+
+        let mut x = vec![Rational::ZERO; n];
+        for (row, &var) in tableau.rows.iter().zip(&tableau.basis) {
+            if var < n {
+                x[var] = row[columns];
+            }
+        }
+        Some((tableau.objective(&phase_two), x))
+    }
+}
+
+/// Simplex tableau in equality form, each row ending with its right-hand side
+struct Tableau {
+    rows: Vec<Vec<Rational>>,
+    /// Basic variable of each row
+    basis: Vec<usize>,
+}
+
+impl Tableau {
+    fn objective(&self, costs: &[Rational]) -> Rational {
+        let rhs = costs.len();
+        self.rows
+            .iter()
+            .zip(&self.basis)
+            .fold(Rational::ZERO, |acc, (row, &var)| {
+                acc + costs[var] * row[rhs]
+            })
+    }
+
+    /// Minimise `costs`, only letting the first `allowed` columns enter the basis.
+    ///
+    /// Uses Bland's rule, which can't cycle. Both phases have non-negative costs over
+    /// non-negative variables, so they are bounded below.
+    fn optimise(&mut self, costs: &[Rational], allowed: usize) {
+        let rhs = costs.len();
+        loop {
+            let reduced = |j: usize| {
+                self.rows
+                    .iter()
+                    .zip(&self.basis)
+                    .fold(costs[j], |acc, (row, &var)| acc - costs[var] * row[j])
+            };
+            let Some(entering) = (0..allowed).find(|&j| reduced(j) < Rational::ZERO) else {
+                return;
+            };
+
+            let leaving = (0..self.rows.len())
+                .filter(|&i| self.rows[i][entering] > Rational::ZERO)
+                .min_by(|&i, &k| {
+                    let ratio = |i: usize| self.rows[i][rhs] / self.rows[i][entering];
+                    ratio(i)
+                        .cmp(&ratio(k))
+                        .then(self.basis[i].cmp(&self.basis[k]))
+                })
+                .expect("non-negative costs are bounded below");
+            self.pivot(leaving, entering);
+        }
+    }
+
+    /// Pivot artificial variables (columns from `real` on) out of the basis after phase one,
+    /// dropping rows that turn out to be redundant
+    fn evict(&mut self, real: usize) {
+        let mut i = 0;
+        while i < self.rows.len() {
+            if self.basis[i] >= real {
+                match (0..real).find(|&j| self.rows[i][j] != Rational::ZERO) {
+                    Some(j) => self.pivot(i, j),
+                    None => {
+                        self.rows.remove(i);
+                        self.basis.remove(i);
+                        continue;
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    fn pivot(&mut self, row: usize, col: usize) {
+        let pivot = self.rows[row][col];
+        self.rows[row].iter_mut().for_each(|v| *v = *v / pivot);
+        let pivot_row = self.rows[row].clone();
+        for (i, other) in self.rows.iter_mut().enumerate() {
+            let factor = other[col];
+            if i != row && factor != Rational::ZERO {
+                for (v, &p) in other.iter_mut().zip(&pivot_row) {
+                    *v = *v - factor * p;
+                }
+            }
+        }
+        self.basis[row] = col;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, -4);
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half + Rational::ONE, Rational::new(1, 2));
+        assert_eq!((half.floor(), half.ceil()), (-1, 0));
+        assert!(half < Rational::ZERO);
+        assert_eq!(Rational::new(7, 3).to_string(), "7/3");
+
+        // Cancelling first keeps these within i128
+        let big = 1i128 << 100;
+        let (x, y) = (Rational::new(big, big + 1), Rational::new(big + 1, big));
+        assert_eq!(x * y, Rational::ONE);
+        assert_eq!(x / x, Rational::ONE);
+        assert_eq!(
+            Rational::new(1, big) + Rational::new(1, big),
+            Rational::new(2, big)
+        );
+        assert!(x < y);
+        assert!(Rational::new(big - 1, big) < Rational::new(big, big + 1));
+        assert!(Rational::new(-big, big + 1) > Rational::new(-big - 1, big + 2));
+
+        let small: Vec<Rational> = (-7..=7)
+            .flat_map(|num| (1..=7).map(move |den| Rational::new(num, den)))
+            .collect();
+        for a in &small {
+            for b in &small {
+                let expected = (a.num * b.den).cmp(&(b.num * a.den));
+                assert_eq!(a.cmp(b), expected, "{a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn test_rational_overflow() {
+        let _ = Rational::from(i128::MAX) + Rational::ONE;
+    }
+
+    #[test]
+    fn test_elimination() {
+        let matrix = vec![vec![2, 4, 1], vec![1, 2, 3], vec![3, 6, 4]];
+        assert_eq!(rank(&matrix), Some(2));
+        assert_eq!(determinant(&matrix), Some(0));
+        assert_eq!(determinant(&[vec![2, 1], vec![7, 4]]), Some(1));
+        assert_eq!(determinant(&[vec![0, 1], vec![1, 0]]), Some(-1));
+        assert_eq!(
+            determinant(&[vec![3, 2, -1], vec![2, -2, 4], vec![-1, 5, -2]]),
+            Some(-56)
+        );
+
+        // Too big for i128 part way through, rather than wrapping to a wrong answer
+        let m = i64::MAX;
+        let huge = [vec![m, 1, 0], vec![1, m, 1], vec![0, 1, m]];
+        let square = (m as i128) * (m as i128);
+        assert_eq!(determinant(&[vec![m, 1], vec![1, m]]), Some(square - 1));
+        assert_eq!(determinant(&huge), None);
+        assert_eq!(rank(&huge), None);
+
+        let basis = nullspace(&matrix).unwrap();
+        assert_eq!(basis, [vec![-2, 1, 0]]);
+        for x in &basis {
+            for row in &matrix {
+                assert_eq!(row.iter().zip(x).map(|(a, b)| a * b).sum::<i64>(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimise_sum() {
+        // Day 10's first example: buttons (3) (1,3) (2) (2,3) (0,2) (0,1), joltages {3,5,4,7}
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let b = [3, 5, 4, 7];
+        let a: Vec<Vec<i64>> = (0..4)
+            .map(|i| buttons.iter().map(|btn| btn.contains(&i) as i64).collect())
+            .collect();
+        let x = minimise_sum(&a, &b).unwrap();
+        assert_eq!(x.iter().sum::<i64>(), 10);
+        for (row, &rhs) in a.iter().zip(&b) {
+            assert_eq!(row.iter().zip(&x).map(|(a, x)| a * x).sum::<i64>(), rhs);
+        }
+    }
+
+    #[test]
+    fn test_minimise_needs_branching() {
+        // The LP optimum is fractional: x = (1.5, 1.5) for 2x + 2y = 6, x + 3y = 6
+        // only has integer solutions with y = 1.5, so there are none
+        assert_eq!(minimise_sum(&[vec![2, 2], vec![1, 3]], &[6, 6]), None);
+
+        // 3x + 5y = 22 with minimal x + y is x = 4, y = 2
+        assert_eq!(minimise_sum(&[vec![3, 5]], &[22]), Some(vec![4, 2]));
+        // Weighted: prefer the cheaper variable even if more of it is needed
+        assert_eq!(minimise(&[1, 10], &[vec![1, 2]], &[4]), Some(vec![4, 0]));
+        // No rows at all: zero is optimal
+        assert_eq!(minimise_sum(&[], &[]), Some(vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "non-negative")]
+    fn test_minimise_negative_costs() {
+        minimise(&[-1], &[vec![1]], &[1]);
+    }
+}
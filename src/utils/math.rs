@@ -0,0 +1,363 @@
+//! Number theory helpers shared between days.
+//!
+//! Modular arithmetic works on `u64` values and multiplies in `u128`, so no
+//! intermediate product can overflow.
+use std::ops::Div;
+
+/// Primitive integer usable with [`gcd`] and [`lcm`]
+pub trait Integer: Copy + Ord + Div<Output = Self> {
+    const ZERO: Self;
+
+    /// Absolute value (identity for unsigned types), None for a signed `MIN`
+    fn checked_magnitude(self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Remainder that gives 0 for a signed `MIN % -1` instead of overflowing
+    fn wrapping_rem(self, other: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $($t:ty),*; signed: $($s:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn checked_magnitude(self) -> Option<Self> {
+                Some(self)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn wrapping_rem(self, other: Self) -> Self {
+                <$t>::wrapping_rem(self, other)
+            }
+        })*
+        $(impl Integer for $s {
+            const ZERO: Self = 0;
+
+            fn checked_magnitude(self) -> Option<Self> {
+                self.checked_abs()
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$s>::checked_mul(self, other)
+            }
+
+            fn wrapping_rem(self, other: Self) -> Self {
+                <$s>::wrapping_rem(self, other)
+            }
+        })*
+    };
+}
+
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize; signed: i8, i16, i32, i64, i128, isize);
+
+/// Greatest common divisor, always non-negative (`gcd(0, 0) == 0`).
+///
+/// Panics if the result doesn't fit in `T`, which only happens for a signed
+/// type whose gcd is `-MIN`, i.e. `gcd(MIN, 0)` and `gcd(MIN, MIN)`.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd doesn't fit in the signed type")
+}
+
+/// Greatest common divisor, or None if it doesn't fit in `T` (see [`gcd`])
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    // Reduce with signed remainders and only take the magnitude at the end, so
+    // `MIN` is fine whenever the gcd itself is smaller
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    a.checked_magnitude()
+}
+
+/// Least common multiple, always non-negative (`lcm(0, x) == 0`).
+///
+/// Panics if the result doesn't fit in `T`, see [`checked_lcm`].
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflows")
+}
+
+/// Least common multiple, or None if it doesn't fit in `T`
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / checked_gcd(a, b)?).checked_mul(b)?.checked_magnitude()
+}
+
+/// Extended Euclid: `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Multiply modulo `modulus` without overflow (panics if `modulus` is 0)
+pub fn mod_mul(a: u64, b: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus must be positive");
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+/// `base^exp mod modulus` by repeated squaring (panics if `modulus` is 0)
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus must be positive");
+    if modulus == 1 {
+        return 0;
+    }
+    let mut result = 1;
+    let mut base = base % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, modulus);
+        }
+        base = mod_mul(base, base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Inverse of `a` modulo `modulus`, if `a` and `modulus` are coprime (never for modulus 0)
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a as i128, modulus as i128);
+    (g == 1).then(|| x.rem_euclid(modulus as i128) as u64)
+}
+
+/// Chinese remainder theorem: the smallest `x` with `x ≡ r (mod m)` for every `(r, m)`,
+/// returned with the combined modulus.
+///
+/// Moduli don't need to be coprime. Returns None if the congruences are
+/// inconsistent, a modulus is 0 or the combined modulus doesn't fit in a `u64`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut m) = (0u128, 1u128);
+    for &(r, n) in congruences {
+        if n == 0 {
+            return None;
+        }
+        let (r, n) = (r as u128 % n as u128, n as u128);
+        // Solve x + m * k ≡ r (mod n) for k
+        let g = gcd(m, n);
+        let diff = (r + n - x % n) % n;
+        if diff % g != 0 {
+            return None;
+        }
+        let n_g = n / g;
+        let inverse = mod_inverse((m / g % n_g) as u64, n_g as u64)? as u128;
+        let k = (diff / g) % n_g * inverse % n_g;
+
+        let combined = m.checked_mul(n_g).filter(|&c| c <= u64::MAX as u128)?;
+        x = (x + m * k) % combined;
+        m = combined;
+    }
+    Some((x as u64, m as u64))
+}
+
+/// Largest `r` with `r^k <= n`
+pub fn iroot(n: u64, k: u32) -> u64 {
+    assert!(k > 0, "zeroth root");
+    if k == 1 || n < 2 {
+        return n;
+    }
+    // Start from the float estimate and correct its rounding error
+    let fits = |r: u64| r.checked_pow(k).is_some_and(|p| p <= n);
+    let mut r = (n as f64).powf(1.0 / k as f64) as u64;
+    while !fits(r) {
+        r -= 1;
+    }
+    while fits(r + 1) {
+        r += 1;
+    }
+    r
+}
+
+/// Largest `r` with `r * r <= n`
+pub fn isqrt(n: u64) -> u64 {
+    n.isqrt()
+}
+
+/// Largest `r` with `r^3 <= n`
+pub fn icbrt(n: u64) -> u64 {
+    iroot(n, 3)
+}
+
+/// All divisors of `n` in increasing order (empty for 0)
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    for d in (1..=isqrt(n)).filter(|&d| n.is_multiple_of(d)) {
+        small.push(d);
+        if d != n / d {
+            large.push(n / d);
+        }
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+/// Möbius function: 0 if `n` has a squared prime factor, otherwise
+/// -1 or 1 for an odd or even number of prime factors
+pub fn mobius(mut n: u64) -> i8 {
+    assert!(n > 0, "mobius(0) is undefined");
+    let mut result = 1;
+    let mut p = 2;
+    while p <= n / p {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        result = -result;
+    }
+    result
+}
+
+/// Möbius function of every number up to `limit`, by a linear sieve (index 0 is unused)
+pub fn mobius_up_to(limit: usize) -> Vec<i8> {
+    let mut mu = vec![1i8; limit + 1];
+    let mut composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+    if limit > 0 {
+        mu[0] = 0;
+    }
+    for i in 2..=limit {
+        if !composite[i] {
+            primes.push(i);
+            mu[i] = -1;
+        }
+        for &p in &primes {
+            if i * p > limit {
+                break;
+            }
+            composite[i * p] = true;
+            if i % p == 0 {
+                mu[i * p] = 0;
+                break;
+            }
+            mu[i * p] = -mu[i];
+        }
+    }
+    mu
+}
+
+/// Number of decimal digits of `n` (1 for 0)
+pub fn digits(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// Multiplier that repeats a `block_len`-digit block `reps` times, e.g. `repunit(2, 3) == 10101`
+/// so that `12 * 10101 == 121212`
+pub fn repunit(block_len: u32, reps: u32) -> u128 {
+    let shift = 10u128.pow(block_len);
+    (0..reps).fold(0, |acc, _| acc * shift + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u8, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0i32, -5), 5);
+        assert_eq!(gcd(0u64, 0), 0);
+        assert_eq!(lcm(4u128, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm(0usize, 6), 0);
+
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(gcd(i64::MIN, -1), 1);
+        assert_eq!(checked_gcd(i64::MIN, 0), None);
+        assert_eq!(checked_gcd(i8::MIN, i8::MIN), None);
+        assert_eq!(checked_lcm(1u64 << 32, 3 << 32), Some(3 << 32));
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(checked_lcm(i64::MIN, 1), None);
+        assert_eq!(checked_lcm(i64::MIN + 1, -1), Some(i64::MAX));
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(1, 0), None);
+
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Non-coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        // Large moduli that would overflow a u64 product
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        assert_eq!(
+            crt(&[(5, p), (7, q)]).map(|(x, _)| (x % p, x % q)),
+            Some((5, 7))
+        );
+        assert_eq!(crt(&[(1, p), (1, q), (1, 65_521)]), None);
+        assert_eq!(crt(&[(2, 3), (0, 0)]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn test_mod_pow_zero_modulus() {
+        mod_pow(2, 3, 0);
+    }
+
+    #[test]
+    fn test_roots() {
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(100), 10);
+        assert_eq!(icbrt(26), 2);
+        assert_eq!(icbrt(27), 3);
+        assert_eq!(icbrt(u64::MAX), 2_642_245);
+        assert_eq!(iroot(u64::MAX, 2), u32::MAX as u64);
+        assert_eq!(iroot(1 << 40, 40), 2);
+    }
+
+    #[test]
+    fn test_divisors_and_mobius() {
+        assert_eq!(divisors(36), [1, 2, 3, 4, 6, 9, 12, 18, 36]);
+        assert_eq!(divisors(13), [1, 13]);
+        assert!(divisors(0).is_empty());
+
+        let sieve = mobius_up_to(30);
+        for (n, &mu) in sieve.iter().enumerate().skip(1) {
+            assert_eq!(mu, mobius(n as u64));
+        }
+        assert_eq!(&sieve[1..11], &[1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits(0), 1);
+        assert_eq!(digits(999), 3);
+        assert_eq!(digits(u64::MAX), 20);
+        assert_eq!(repunit(2, 3), 10101);
+        assert_eq!(repunit(1, 0), 0);
+    }
+}
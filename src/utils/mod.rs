@@ -1,10 +1,28 @@
+pub mod direction;
+pub mod dsu;
+pub mod graph;
 pub mod grid;
 pub mod input;
+pub mod interval;
+pub mod kdtree;
+pub mod linalg;
+pub mod math;
+pub mod parse;
 pub mod point;
+pub mod render;
+pub mod sparse_grid;
 
 // Re-export commonly used items
-pub use grid::Grid;
+pub use direction::Direction;
+pub use dsu::DisjointSet;
+pub use graph::Graph;
+pub use grid::{Grid, GridView, RaggedRowError};
 pub use input::{
     parse_lines, read_grid, read_groups, read_input, read_ints, read_ints64, read_lines,
+    resolve_input, InputConfig, InputSource,
 };
-pub use point::Point;
+pub use interval::IntervalSet;
+pub use kdtree::KdTree;
+pub use parse::ParseError;
+pub use point::{Point, Point3};
+pub use sparse_grid::SparseGrid;
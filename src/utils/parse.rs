@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error produced when puzzle input doesn't match the expected format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column (in characters)
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Create an error pointing at `fragment`, which must be a subslice of `input`
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= input.len())
+            .expect("fragment is not part of the input");
+
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        Self::new(line, column, message)
+    }
+
+    /// Create an error pointing just past the end of `input`
+    pub fn at_end(input: &str, message: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse `fragment` (a subslice of `input`) as a number
+pub fn parse_number<T: FromStr>(input: &str, fragment: &str) -> Result<T, ParseError> {
+    fragment
+        .parse()
+        .map_err(|_| ParseError::at(input, fragment, format!("invalid number {fragment:?}")))
+}
+
+/// Non-empty lines of `input`, trimmed of surrounding whitespace
+pub fn trimmed_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().map(str::trim).filter(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = "12,34\n56,x7";
+        let fragment = &input[9..];
+        let error = ParseError::at(input, fragment, "bad");
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.to_string(), "line 2, column 4: bad");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let input = "1 two";
+        assert_eq!(parse_number::<u32>(input, &input[..1]), Ok(1));
+        let error = parse_number::<u32>(input, &input[2..]).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+}
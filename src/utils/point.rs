@@ -1,24 +1,91 @@
-use std::ops::{Add, Sub};
+use super::direction::Direction;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+/// Numeric type usable as a point coordinate
+pub trait Coord:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
 }
 
-impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
+impl<T> Coord for T where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>
+{
+}
+
+/// Absolute difference that also works for unsigned coordinates
+fn distance<T: Coord>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// 2D point, `i32` by default as used to index a [`Grid`](super::Grid)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+/// 3D point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+}
 
+impl<T: Coord> Point<T> {
     /// Manhattan distance to another point
-    pub fn manhattan_distance(&self, other: &Point) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// Chebyshev (king move) distance to another point
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+
+    /// Squared Euclidean distance to another point (avoids sqrt, fine for comparisons)
+    pub fn squared_distance(&self, other: &Self) -> T {
+        let (dx, dy) = (distance(self.x, other.x), distance(self.y, other.y));
+        dx * dx + dy * dy
     }
 
+    /// Component-wise minimum
+    pub fn min(&self, other: &Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Component-wise maximum
+    pub fn max(&self, other: &Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Point<T> {
+    /// Rotate 90 degrees clockwise around the origin (with `y` growing downwards)
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotate 90 degrees counter-clockwise around the origin (with `y` growing downwards)
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl Point {
     /// Get all 4 cardinal neighbors (up, down, left, right)
-    pub fn neighbors4(&self) -> Vec<Point> {
-        vec![
+    pub fn neighbors4(&self) -> [Point; 4] {
+        [
             Point::new(self.x, self.y - 1), // up
             Point::new(self.x, self.y + 1), // down
             Point::new(self.x - 1, self.y), // left
@@ -27,8 +94,8 @@ impl Point {
     }
 
     /// Get all 8 neighbors (cardinal + diagonal)
-    pub fn neighbors8(&self) -> Vec<Point> {
-        vec![
+    pub fn neighbors8(&self) -> [Point; 8] {
+        [
             Point::new(self.x - 1, self.y - 1), // top-left
             Point::new(self.x, self.y - 1),     // top
             Point::new(self.x + 1, self.y - 1), // top-right
@@ -44,26 +111,159 @@ impl Point {
     pub fn move_by(&self, dx: i32, dy: i32) -> Point {
         Point::new(self.x + dx, self.y + dy)
     }
+
+    /// Take a single step in a direction
+    pub fn step(&self, direction: Direction) -> Point {
+        *self + direction.delta()
+    }
 }
 
-impl Add for Point {
-    type Output = Point;
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    /// Manhattan distance to another point
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    /// Chebyshev distance to another point
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        distance(self.x, other.x)
+            .max(distance(self.y, other.y))
+            .max(distance(self.z, other.z))
+    }
+
+    /// Squared Euclidean distance to another point (avoids sqrt, fine for comparisons)
+    pub fn squared_distance(&self, other: &Self) -> T {
+        let dx = distance(self.x, other.x);
+        let dy = distance(self.y, other.y);
+        let dz = distance(self.z, other.z);
+        dx * dx + dy * dy + dz * dz
+    }
+
+    /// Component-wise minimum
+    pub fn min(&self, other: &Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
 
-    fn add(self, other: Point) -> Point {
-        Point::new(self.x + other.x, self.y + other.y)
+    /// Component-wise maximum
+    pub fn max(&self, other: &Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
     }
 }
 
-impl Sub for Point {
+impl<T: Coord + Neg<Output = T>> Point3<T> {
+    /// Rotate 90 degrees around the x axis (right-handed)
+    pub fn rotate_x(&self) -> Self {
+        Self::new(self.x, -self.z, self.y)
+    }
+
+    /// Rotate 90 degrees around the y axis (right-handed)
+    pub fn rotate_y(&self) -> Self {
+        Self::new(self.z, self.y, -self.x)
+    }
+
+    /// Rotate 90 degrees around the z axis (right-handed)
+    pub fn rotate_z(&self) -> Self {
+        Self::new(-self.y, self.x, self.z)
+    }
+
+    /// The point under all 24 rotations that keep the axes axis-aligned, identity first
+    pub fn orientations(&self) -> [Self; 24] {
+        // Point each of the 6 faces along +x, then spin around x
+        let mut faces = [*self; 6];
+        for i in 1..4 {
+            faces[i] = faces[i - 1].rotate_z();
+        }
+        faces[4] = self.rotate_y();
+        faces[5] = self.rotate_y().rotate_y().rotate_y();
+
+        let mut all = [*self; 24];
+        for (i, face) in faces.into_iter().enumerate() {
+            let mut point = face;
+            for spin in 0..4 {
+                all[i * 4 + spin] = point;
+                point = point.rotate_x();
+            }
+        }
+        all
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        /// Scale every component
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                $(self.$field += other.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                $(self.$field -= other.$field;)+
+            }
+        }
+    };
+}
+
+impl_ops!(Point { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl Add<Direction> for Point {
     type Output = Point;
 
-    fn sub(self, other: Point) -> Point {
-        Point::new(self.x - other.x, self.y - other.y)
+    fn add(self, direction: Direction) -> Point {
+        self + direction.delta()
     }
 }
 
-impl From<(i32, i32)> for Point {
-    fn from((x, y): (i32, i32)) -> Self {
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
         Point::new(x, y)
     }
 }
@@ -73,3 +273,54 @@ impl From<(usize, usize)> for Point {
         Point::new(x as i32, y as i32)
     }
 }
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 1));
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.squared_distance(&b), 25);
+
+        // Unsigned coordinates don't underflow
+        let (a, b) = (Point::<u64>::new(5, 1), Point::new(2, 7));
+        assert_eq!(a.manhattan_distance(&b), 9);
+        assert_eq!(a.min(&b), Point::new(2, 1));
+        assert_eq!(a.max(&b), Point::new(5, 7));
+
+        let (a, b) = (Point3::new(1, 2, 3), Point3::new(4, 0, 3));
+        assert_eq!(a.manhattan_distance(&b), 5);
+        assert_eq!(a.chebyshev_distance(&b), 3);
+        assert_eq!(a.squared_distance(&b), 13);
+    }
+
+    #[test]
+    fn test_ops_and_rotations() {
+        let p = Point::new(2, -1);
+        assert_eq!(p * 3 - p, Point::new(4, -2));
+        assert_eq!(-p + p, Point::default());
+        assert_eq!(
+            Direction::Up.delta().rotate_right(),
+            Direction::Right.delta()
+        );
+        assert_eq!(p.rotate_left().rotate_right(), p);
+
+        let q = Point3::new(1, 2, 3);
+        assert_eq!(q.rotate_z(), Point3::new(-2, 1, 3));
+        let orientations: HashSet<_> = q.orientations().into_iter().collect();
+        assert_eq!(orientations.len(), 24);
+        assert!(orientations
+            .iter()
+            .all(|o| o.squared_distance(&Point3::default()) == 14));
+    }
+}
@@ -0,0 +1,291 @@
+//! Render grids for debugging: PPM/PGM images, ANSI-coloured terminal output
+//! and frame-by-frame animations.
+//!
+//! Images are written as binary netpbm files, which most image viewers open
+//! directly and `ffmpeg -i frame_%05d.ppm out.mp4` turns into a video.
+use super::grid::Grid;
+use super::point::Point;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// 24-bit colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+}
+
+/// Write a grid as a binary PPM (P6) image, drawing each cell as a `scale` x `scale` square
+pub fn write_ppm<T>(
+    out: &mut impl Write,
+    grid: &Grid<T>,
+    scale: usize,
+    colour: impl Fn(&T) -> Rgb,
+) -> io::Result<()> {
+    write_netpbm(out, "P6", grid, scale, |cell| {
+        let Rgb(r, g, b) = colour(cell);
+        [r, g, b]
+    })
+}
+
+/// Write a grid as a binary PGM (P5) greyscale image, drawing each cell as a `scale` x `scale` square
+pub fn write_pgm<T>(
+    out: &mut impl Write,
+    grid: &Grid<T>,
+    scale: usize,
+    shade: impl Fn(&T) -> u8,
+) -> io::Result<()> {
+    write_netpbm(out, "P5", grid, scale, |cell| [shade(cell)])
+}
+
+fn write_netpbm<T, const N: usize>(
+    out: &mut impl Write,
+    magic: &str,
+    grid: &Grid<T>,
+    scale: usize,
+    pixel: impl Fn(&T) -> [u8; N],
+) -> io::Result<()> {
+    let scale = scale.max(1);
+    writeln!(
+        out,
+        "{magic}\n{} {}\n255",
+        grid.width * scale,
+        grid.height * scale
+    )?;
+
+    let mut line = Vec::with_capacity(grid.width * scale * N);
+    for row in grid.rows() {
+        line.clear();
+        for cell in row {
+            let pixel = pixel(cell);
+            for _ in 0..scale {
+                line.extend_from_slice(&pixel);
+            }
+        }
+        for _ in 0..scale {
+            out.write_all(&line)?;
+        }
+    }
+    out.flush()
+}
+
+/// How a single cell is drawn in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub glyph: char,
+    pub foreground: Option<Rgb>,
+    pub background: Option<Rgb>,
+}
+
+impl Style {
+    /// Glyph in the terminal's default colours
+    pub fn plain(glyph: char) -> Self {
+        Self {
+            glyph,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    pub fn fg(self, colour: Rgb) -> Self {
+        Self {
+            foreground: Some(colour),
+            ..self
+        }
+    }
+
+    pub fn bg(self, colour: Rgb) -> Self {
+        Self {
+            background: Some(colour),
+            ..self
+        }
+    }
+}
+
+/// Render a grid as text with ANSI 24-bit colour escapes, one line per row
+pub fn ansi<T>(grid: &Grid<T>, style: impl Fn(Point, &T) -> Style) -> String {
+    let mut out = String::new();
+    for (y, row) in grid.rows().enumerate() {
+        let mut current = (None, None);
+        for (x, cell) in row.iter().enumerate() {
+            let Style {
+                glyph,
+                foreground,
+                background,
+            } = style(Point::new(x as i32, y as i32), cell);
+
+            // Only emit escapes when the colours change
+            if (foreground, background) != current {
+                out.push_str("\x1b[0m");
+                if let Some(Rgb(r, g, b)) = foreground {
+                    write!(out, "\x1b[38;2;{r};{g};{b}m").unwrap();
+                }
+                if let Some(Rgb(r, g, b)) = background {
+                    write!(out, "\x1b[48;2;{r};{g};{b}m").unwrap();
+                }
+                current = (foreground, background);
+            }
+            out.push(glyph);
+        }
+        if current != (None, None) {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Render a character grid with the given points drawn on a coloured background
+pub fn highlight(grid: &Grid<char>, points: &HashSet<Point>, colour: Rgb) -> String {
+    ansi(grid, |point, &glyph| {
+        let style = Style::plain(glyph);
+        if points.contains(&point) {
+            style.bg(colour)
+        } else {
+            style
+        }
+    })
+}
+
+/// Plays frames in the terminal, redrawing over the previous frame
+pub struct Animation<W: Write> {
+    out: W,
+    delay: Duration,
+    frames: usize,
+}
+
+impl Animation<io::Stdout> {
+    /// Animate on stdout, pausing `delay` after every frame
+    pub fn stdout(delay: Duration) -> Self {
+        Self::new(io::stdout(), delay)
+    }
+}
+
+impl<W: Write> Animation<W> {
+    pub fn new(out: W, delay: Duration) -> Self {
+        Self {
+            out,
+            delay,
+            frames: 0,
+        }
+    }
+
+    /// Number of frames shown so far
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Clear the screen and draw a frame, e.g. the output of [`ansi`]
+    pub fn frame(&mut self, frame: &str) -> io::Result<()> {
+        write!(self.out, "\x1b[H\x1b[2J{frame}")?;
+        self.out.flush()?;
+        self.frames += 1;
+        if !self.delay.is_zero() {
+            thread::sleep(self.delay);
+        }
+        Ok(())
+    }
+}
+
+/// Writes numbered image frames, `frame_00000.ppm`, `frame_00001.ppm`, ... into a directory
+pub struct ImageSequence {
+    dir: PathBuf,
+    frames: usize,
+}
+
+impl ImageSequence {
+    /// Start a sequence in `dir`, creating it if needed
+    pub fn create(dir: impl AsRef<Path>) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            frames: 0,
+        })
+    }
+
+    /// Number of frames written so far
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Write the next frame, returning its path
+    pub fn frame<T>(
+        &mut self,
+        grid: &Grid<T>,
+        scale: usize,
+        colour: impl Fn(&T) -> Rgb,
+    ) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!("frame_{:05}.ppm", self.frames));
+        let mut out = BufWriter::new(File::create(&path)?);
+        write_ppm(&mut out, grid, scale, colour)?;
+        self.frames += 1;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_netpbm() {
+        let grid = Grid::from_string("#.\n..");
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, &grid, 1, |&c| {
+            if c == '#' {
+                Rgb::RED
+            } else {
+                Rgb::BLACK
+            }
+        })
+        .unwrap();
+        assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(&ppm[11..14], &[220, 50, 47]);
+        assert_eq!(ppm.len(), 11 + 2 * 2 * 3);
+
+        let mut pgm = Vec::new();
+        write_pgm(&mut pgm, &grid, 3, |&c| if c == '#' { 255 } else { 0 }).unwrap();
+        assert!(pgm.starts_with(b"P5\n6 6\n255\n"));
+        assert_eq!(&pgm[11..17], &[255, 255, 255, 0, 0, 0]);
+        assert_eq!(pgm.len(), 11 + 6 * 6);
+    }
+
+    #[test]
+    fn test_ansi() {
+        let grid = Grid::from_string("ab\ncd");
+        assert_eq!(ansi(&grid, |_, &c| Style::plain(c)), "ab\ncd\n");
+
+        let points = HashSet::from([Point::new(1, 0)]);
+        let text = highlight(&grid, &points, Rgb(1, 2, 3));
+        assert_eq!(text, "a\x1b[0m\x1b[48;2;1;2;3mb\x1b[0m\ncd\n");
+    }
+
+    #[test]
+    fn test_frames() {
+        let mut animation = Animation::new(Vec::new(), Duration::ZERO);
+        animation.frame("x\n").unwrap();
+        animation.frame("y\n").unwrap();
+        assert_eq!(animation.frames(), 2);
+        assert_eq!(animation.out, b"\x1b[H\x1b[2Jx\n\x1b[H\x1b[2Jy\n");
+
+        let dir = std::env::temp_dir().join(format!("aoc-25-render-{}", std::process::id()));
+        let mut sequence = ImageSequence::create(&dir).unwrap();
+        let grid = Grid::new(2, 1, 0u8);
+        sequence.frame(&grid, 1, |_| Rgb::WHITE).unwrap();
+        let path = sequence.frame(&grid, 1, |_| Rgb::WHITE).unwrap();
+        assert_eq!(path, dir.join("frame_00001.ppm"));
+        assert_eq!(fs::read(&path).unwrap().len(), 11 + 6);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,215 @@
+use super::grid::Grid;
+use super::point::Point;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+/// Unbounded grid storing only occupied cells, keyed by [`Point`] (coordinates may be negative)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    /// Inclusive corners of the bounding box of all occupied cells
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sparse copy of a dense grid, keeping only the cells where `keep` holds
+    pub fn from_grid(grid: Grid<T>, keep: impl Fn(&T) -> bool) -> Self {
+        let points: Vec<Point> = grid.points().collect();
+        points
+            .into_iter()
+            .zip(grid.into_rows().into_iter().flatten())
+            .filter(|(_, cell)| keep(cell))
+            .collect()
+    }
+
+    /// Number of occupied cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Get a value at a point (returns None if unoccupied)
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.cells.get(point)
+    }
+
+    /// Get a mutable reference to a value at a point
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.cells.get_mut(point)
+    }
+
+    /// Check if a point is occupied
+    pub fn contains(&self, point: &Point) -> bool {
+        self.cells.contains_key(point)
+    }
+
+    /// Set a value at a point, returning the previous value
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+        });
+        self.cells.insert(point, value)
+    }
+
+    /// Clear a point, returning its value
+    pub fn remove(&mut self, point: &Point) -> Option<T> {
+        let value = self.cells.remove(point)?;
+        // Only removing a cell on the edge of the bounding box can shrink it
+        if let Some((min, max)) = self.bounds {
+            if point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y {
+                self.bounds = self.compute_bounds();
+            }
+        }
+        Some(value)
+    }
+
+    /// Inclusive top-left and bottom-right corners of all occupied cells
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    fn compute_bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Iterate over occupied cells with their positions, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.cells.iter().map(|(&point, cell)| (point, cell))
+    }
+
+    /// Iterate over occupied points, in no particular order
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Occupied neighbors (4-directional) of a point together with their values
+    pub fn neighbors4(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        point
+            .neighbors4()
+            .into_iter()
+            .filter_map(move |p| self.get(&p).map(|cell| (p, cell)))
+    }
+
+    /// Occupied neighbors (8-directional) of a point together with their values
+    pub fn neighbors8(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        point
+            .neighbors8()
+            .into_iter()
+            .filter_map(move |p| self.get(&p).map(|cell| (p, cell)))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Dense copy covering the bounding box, with unoccupied cells set to `fill`.
+    ///
+    /// Cell `(0, 0)` of the result is the top-left corner of [`SparseGrid::bounds`].
+    pub fn to_grid(&self, fill: T) -> Grid<T> {
+        let Some((min, max)) = self.bounds else {
+            return Grid::new(0, 0, fill);
+        };
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut grid = Grid::new(width, height, fill);
+        for (point, cell) in self.iter() {
+            grid[point - min] = cell.clone();
+        }
+        grid
+    }
+}
+
+impl<T: fmt::Display> SparseGrid<T> {
+    /// Draw the bounding box as text, with `empty` for unoccupied cells
+    pub fn render(&self, empty: char) -> String {
+        let mut out = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    match self.get(&Point::new(x, y)) {
+                        Some(cell) => write!(out, "{cell}").unwrap(),
+                        None => out.push(empty),
+                    }
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::from_grid(grid, |_| true)
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point::new(-2, 3), '#');
+        grid.insert(Point::new(4, -1), '#');
+        grid.insert(Point::new(0, 0), '#');
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(4, 3))));
+
+        grid.remove(&Point::new(4, -1));
+        assert_eq!(grid.bounds(), Some((Point::new(-2, 0), Point::new(0, 3))));
+        assert_eq!(grid.neighbors8(&Point::new(-1, 1)).count(), 1);
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let dense = Grid::from_string("#..\n.#.\n..#");
+        let sparse = SparseGrid::from_grid(dense.clone(), |&c| c == '#');
+        assert_eq!(sparse.len(), 3);
+        assert_eq!(sparse.render('.'), "#..\n.#.\n..#\n");
+        assert_eq!(sparse.to_grid('.'), dense);
+        assert_eq!(SparseGrid::from(dense).len(), 9);
+    }
+}
@@ -1,27 +1,24 @@
 //! # Day 1: Dial Puzzle
 
-pub fn parse(input: &str) -> Vec<i32> {
-    let mut result = Vec::new();
+use crate::utils::parse::{parse_number, trimmed_lines, ParseError};
 
-    for line in input.lines() {
-        let chars: Vec<char> = line.chars().collect();
-        if chars.len() < 2 {
-            continue;
-        }
+pub type ParsedInput = Vec<i32>;
 
-        let direction = match chars[0] {
-            'L' => false,
-            'R' => true,
-            _ => continue,
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+    let mut result = Vec::new();
+
+    for line in trimmed_lines(input) {
+        let direction = match line.chars().next() {
+            Some('L') => false,
+            Some('R') => true,
+            _ => return Err(ParseError::at(input, line, "expected L or R")),
         };
 
-        let number: String = chars[1..].iter().collect();
-        if let Ok(steps) = number.parse::<i32>() {
-            result.push(if direction { steps } else { -steps });
-        }
+        let steps: i32 = parse_number(input, &line[1..])?;
+        result.push(if direction { steps } else { -steps });
     }
 
-    result
+    Ok(result)
 }
 
 pub fn part1(input: &[i32]) -> u32 {
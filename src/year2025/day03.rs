@@ -1,20 +1,15 @@
-use std::collections::HashMap;
+use crate::utils::parse::{trimmed_lines, ParseError};
 
-type ParsedInput = Vec<Vec<u8>>;
+pub type ParsedInput = Vec<Vec<u8>>;
 
-pub fn parse(input: &str) -> ParsedInput {
-    input
-        .trim()
-        .lines()
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+    trimmed_lines(input)
         .map(|line| {
-            line.trim()
-                .bytes()
-                .filter_map(|b| {
-                    if b.is_ascii_digit() {
-                        Some(b - b'0')
-                    } else {
-                        None
-                    }
+            line.char_indices()
+                .map(|(i, c)| {
+                    c.to_digit(10)
+                        .map(|digit| digit as u8)
+                        .ok_or_else(|| ParseError::at(input, &line[i..], "expected a digit"))
                 })
                 .collect()
         })
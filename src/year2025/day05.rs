@@ -1,87 +1,46 @@
-type ParsedInput = (Vec<(u64, u64)>, Vec<u64>);
+use crate::utils::parse::{parse_number, trimmed_lines, ParseError};
+use crate::utils::IntervalSet;
 
-pub fn parse(input: &str) -> ParsedInput {
+pub type ParsedInput = (IntervalSet<u64>, Vec<u64>);
+
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
     // First part is a list of ranges, two numbers per line separated by '-'
     // Second part is a list of IDs, one per line
     // The sections are separated by a blank line
-    let mut sections = input.trim().split("\n\n");
-    let ranges_section = sections.next().unwrap();
-    let ids_section = sections.next().unwrap();
-
-    let mut ranges: Vec<(u64, u64)> = ranges_section
-        .lines()
-        .filter_map(|line| {
-            let mut parts = line.trim().split('-');
-            if let (Some(start_str), Some(end_str)) = (parts.next(), parts.next()) {
-                if let (Ok(start), Ok(end)) = (start_str.parse::<u64>(), end_str.parse::<u64>()) {
-                    return Some((start, end));
-                }
-            }
-            None
+    let trimmed = input.trim();
+    let (ranges_section, ids_section) = trimmed
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::at_end(input, "missing blank line between ranges and IDs"))?;
+
+    // Overlapping and touching ranges are merged as they are inserted
+    let ranges: IntervalSet<u64> = trimmed_lines(ranges_section)
+        .map(|line| {
+            let (start, end) = line
+                .split_once('-')
+                .ok_or_else(|| ParseError::at(input, line, "expected a range like 3-5"))?;
+            Ok(parse_number(input, start)?..=parse_number(input, end)?)
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    let ids: Vec<u64> = ids_section
-        .lines()
-        .filter_map(|line| line.trim().parse::<u64>().ok())
-        .collect();
+    let ids: Vec<u64> = trimmed_lines(ids_section)
+        .map(|line| parse_number(input, line))
+        .collect::<Result<_, ParseError>>()?;
 
-    // Merge overlapping ranges during parsing
-    ranges.sort_by_key(|r| r.0);
-    let mut merged_ranges: Vec<(u64, u64)> = Vec::new();
-    for range in ranges {
-        if let Some(last) = merged_ranges.last_mut() {
-            if range.0 <= last.1 + 1 {
-                // Overlapping or contiguous ranges, merge them
-                if range.1 > last.1 {
-                    last.1 = range.1;
-                }
-            } else {
-                // Non-overlapping range, add it to the list
-                merged_ranges.push(range);
-            }
-        } else {
-            // First range, just add it
-            merged_ranges.push(range);
-        }
-    }
-
-    (merged_ranges, ids)
+    Ok((ranges, ids))
 }
 
 pub fn part1(_input: &ParsedInput) -> u32 {
-    // Check each ID, see if it is in any of the ranges
+    // Count the IDs that fall in any of the ranges
     let (ranges, ids) = _input;
-    let mut valid_count: u32 = 0;
-
-    // Iterate over each ID, and check if it is in any of the ranges
-    for id in ids {
-        for range in ranges.iter() {
-            if id_in_range(*id, range) {
-                valid_count += 1;
-                break;
-            }
-        }
-    }
-
-    valid_count
-}
-
-// Helper to check if an ID is in any of the ranges
-pub fn id_in_range(id: u64, range: &(u64, u64)) -> bool {
-    id >= range.0 && id <= range.1
+    ids.iter().filter(|&&id| ranges.contains(id)).count() as u32
 }
 
-pub fn part2(_input: &ParsedInput) -> u64 {
+pub fn part2(_input: &ParsedInput) -> u128 {
     // Now we have to count the total number of valid IDs in the ranges
-    // We have to first handle overlapping ranges
-    // Then we can sum up the sizes of the non-overlapping ranges
-    // Ranges are already merged in parse, so just sum up their sizes
+    // Ranges are already merged in parse, and their total is counted in u128
+    // as `0-18446744073709551615` alone holds more IDs than a u64 can count
     let (ranges, _ids) = _input;
-
-    let mut total_count: u64 = 0;
-    for range in ranges.iter() {
-        total_count += (range.1 - range.0 + 1) as u64;
-    }
-    total_count
+    ranges
+        .covered_len()
+        .expect("a set of u64 ranges always fits in u128")
 }
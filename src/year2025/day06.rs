@@ -1,134 +1,73 @@
-use crate::utils::{Grid, Point};
+use crate::utils::{Grid, ParseError};
 
-type ParsedInput = Vec<(char, Grid<char>)>;
+pub type ParsedInput = Vec<(char, Grid<char>)>;
 
-pub fn parse(input: &str) -> ParsedInput {
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
     // we have several lines, all except the last line consist of numbers
     // the lines are space-separated columns, but there could be varying amounts of spaces
     // The last line consists of a single char per column, and this will always be at the
     // first "index" of that column.
-    // So, our approach will be to read the last line first to get the characters,
-    // and at the same time we will find the width of each column.
-    // Then we read each column into a grid of chars.
+    // Columns are separated by a column of spaces running through every line, so we
+    // read everything into one grid and split it there.
 
     // First, find the height of the grid (number of lines - 1)
-    let lines: Vec<&str> = input.lines().collect();
+    let lines: Vec<&str> = input.lines().filter(|line| !line.trim().is_empty()).collect();
+    let Some((last_line, number_lines)) = lines.split_last().filter(|(_, rest)| !rest.is_empty())
+    else {
+        return Err(ParseError::at_end(input, "expected number rows followed by an operator row"));
+    };
     let height = lines.len() - 1;
 
-    // Iterate over the last line to find the characters and their positions
-    let last_line = lines.last().unwrap();
-    let mut char_positions: Vec<(char, usize)> = Vec::new();
-    for (i, ch) in last_line.chars().enumerate() {
-        if ch != ' ' {
-            char_positions.push((ch, i));
+    // Only digits and spaces are allowed in the number rows
+    for line in number_lines {
+        if let Some(i) = line.find(|c: char| !c.is_ascii_digit() && c != ' ') {
+            return Err(ParseError::at(input, &line[i..], "expected a digit or space"));
         }
     }
 
-    // Now determine column boundaries
-    // Each column starts at the character position and ends before the next column starts
-    let mut columns: Vec<(char, Vec<Vec<char>>)> = Vec::new();
-    for (idx, &(ch, pos)) in char_positions.iter().enumerate() {
-        let col_start = pos;
-        let col_end = if idx + 1 < char_positions.len() {
-            char_positions[idx + 1].1
-        } else {
-            last_line.len()
-        };
-
-        // Now read the column data from the previous lines
-        let mut grid_data: Vec<Vec<char>> = Vec::new();
-        for line in &lines[0..height] {
-            let col_str = if col_end <= line.len() {
-                &line[col_start..col_end]
-            } else {
-                &line[col_start..]
-            };
-            let row: Vec<char> = col_str.chars().collect();
-            grid_data.push(row);
+    // The operator row may only hold operators and spaces
+    for (i, ch) in last_line.char_indices() {
+        if !matches!(ch, '+' | '*' | ' ') {
+            return Err(ParseError::at(input, &last_line[i..], "expected '+' or '*'"));
         }
-        columns.push((ch, grid_data));
     }
 
-    // Convert columns to ParsedInput
-    columns
-        .into_iter()
-        .map(|(ch, grid_data)| (ch, Grid::from_vec(grid_data)))
-        .collect()
-}
-
-pub fn part1(_input: &ParsedInput) -> u64 {
-    // Iterate over each (char, Grid<char>) pair
-    // We should look at the chat to decide if we should sum (+) or multiply (*)
-    // Then we add all chars per row and convert to u64, i.e. '3', '4', '5' -> 345
-    let mut total: u64 = 0;
-    for (ch, grid) in _input {
-        let mut column_numbers: Vec<u64> = Vec::new();
-        for y in 0..grid.height {
-            let mut row_value: u64 = 0;
-            for x in 0..grid.width {
-                if let Some(c) = grid.get(&Point {
-                    x: x as i32,
-                    y: y as i32,
-                }) {
-                    if c.is_ascii_digit() {
-                        row_value = row_value * 10 + (*c as u64 - '0' as u64);
-                    }
-                }
-            }
-            column_numbers.push(row_value);
-        }
-        // Now we have all row values for this column
-        // Depending on ch, we either sum or multiply
-        let column_total: u64 = if *ch == '+' {
-            column_numbers.iter().sum()
-        } else if *ch == '*' {
-            column_numbers.iter().product()
-        } else {
-            0
+    // Pad the lines into a rectangle and split it into problems at all-blank columns.
+    // Each problem's operator sits somewhere in its bottom row.
+    let grid = Grid::from_string(&lines.join("\n"));
+    let mut problems = Vec::new();
+    for block in grid.split_columns(|&c| c == ' ') {
+        let Some(&op) = block.row(height).iter().find(|&&c| c != ' ') else {
+            return Err(ParseError::at(input, last_line, "expected an operator for every column"));
         };
-        total += column_total;
+        let numbers = block.view(0, 0, block.width(), height).unwrap();
+        problems.push((op, numbers.to_grid()));
     }
 
-    total
+    Ok(problems)
 }
 
-pub fn part2(_input: &ParsedInput) -> u64 {
-    // Iterate over the input similarly to part1
-    let mut total: u64 = 0;
-    for (ch, grid) in _input {
-        let mut column_numbers: Vec<u64> = Vec::new();
-        // Now we have to produce the numbers to "operate" on
-        // from top to bottom, instead of left to right.
-        // The easiest way is to push chars into a string per column
-        // and then parse that string as a u64.
-        for x in 0..grid.width {
-            let mut col_string = String::new();
-            for y in 0..grid.height {
-                if let Some(c) = grid.get(&Point {
-                    x: x as i32,
-                    y: y as i32,
-                }) {
-                    if c.is_ascii_digit() {
-                        col_string.push(*c);
-                    }
-                }
-            }
-            if !col_string.is_empty() {
-                if let Ok(value) = col_string.parse::<u64>() {
-                    column_numbers.push(value);
-                }
-            }
-        }
-        // Now we have all column values for this column
-        // Depending on ch, we either sum or multiply
-        let column_total: u64 = if *ch == '+' {
-            column_numbers.iter().sum()
-        } else if *ch == '*' {
-            column_numbers.iter().product()
-        } else {
-            0
-        };
-        total += column_total;
+pub fn part1(input: &ParsedInput) -> u64 {
+    // Every row of a problem is a number, read left to right
+    input.iter().map(|(op, grid)| evaluate(*op, grid.rows())).sum()
+}
+
+pub fn part2(input: &ParsedInput) -> u64 {
+    // Every column of a problem is a number, read top to bottom
+    input
+        .iter()
+        .map(|(op, grid)| evaluate(*op, grid.transpose().rows()))
+        .sum()
+}
+
+/// Sum (+) or multiply (*) the numbers spelled out by the digits of each line
+fn evaluate<'a>(op: char, lines: impl Iterator<Item = &'a [char]>) -> u64 {
+    let numbers = lines.filter_map(|line| {
+        let digits: String = line.iter().filter(|c| c.is_ascii_digit()).collect();
+        digits.parse::<u64>().ok()
+    });
+    match op {
+        '+' => numbers.sum(),
+        _ => numbers.product(),
     }
-    total
 }
@@ -1,14 +1,20 @@
-use crate::utils::{Grid, Point};
+use crate::utils::{Grid, ParseError, Point};
 
-type ParsedInput = Grid<char>;
+pub type ParsedInput = Grid<char>;
 
-pub fn parse(input: &str) -> ParsedInput {
-    let cells = input
-        .lines()
-        .map(|s| s.chars().collect())
-        .collect::<Vec<Vec<char>>>();
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    for line in &lines {
+        if let Some(i) = line.find(|c| !matches!(c, 'S' | '.' | '^')) {
+            return Err(ParseError::at(input, &line[i..], "expected 'S', '.' or '^'"));
+        }
+    }
 
-    Grid::from_vec(cells)
+    let cells = lines.iter().map(|line| line.chars().collect()).collect();
+    Grid::try_from_vec(cells).map_err(|e| {
+        let message = format!("row has {} cells, expected {}", e.found, e.expected);
+        ParseError::at(input, lines[e.row], message)
+    })
 }
 
 pub fn part1(_input: &ParsedInput) -> u32 {
@@ -114,8 +120,6 @@ pub fn part2(_input: &ParsedInput) -> u64 {
             .map(|(p, &count)| (*p, count))
             .collect();
 
-        if !current_layer.is_empty() {}
-
         for (pos, count) in current_layer {
             let cell = grid.get(&pos);
             match cell {
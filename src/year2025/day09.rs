@@ -1,15 +1,20 @@
+use crate::utils::parse::{parse_number, trimmed_lines, ParseError};
+use crate::utils::Point;
 use rayon::prelude::*;
 
-type ParsedInput = Vec<(u64, u64)>;
+pub type ParsedInput = Vec<Point<u64>>;
+type Edge = (Point<u64>, Point<u64>);
 
-pub fn parse(input: &str) -> ParsedInput {
-    input
-        .lines()
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+    trimmed_lines(input)
         .map(|line| {
-            let mut parts = line.split(',');
-            let x = parts.next().unwrap().parse().unwrap();
-            let y = parts.next().unwrap().parse().unwrap();
-            (x, y)
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| ParseError::at(input, line, "expected two comma-separated numbers"))?;
+            Ok(Point::new(
+                parse_number(input, x.trim())?,
+                parse_number(input, y.trim())?,
+            ))
         })
         .collect()
 }
@@ -22,11 +27,8 @@ pub fn part1(_input: &ParsedInput) -> u64 {
     for i in 0.._input.len() {
         for j in 0.._input.len() {
             if i != j {
-                let (x1, y1) = _input[i];
-                let (x2, y2) = _input[j];
-                let width = if x2 > x1 { x2 - x1 } else { x1 - x2 };
-                let height = if y2 > y1 { y2 - y1 } else { y1 - y2 };
-                let area = (width + 1) * (height + 1);
+                let size = _input[i].max(&_input[j]) - _input[i].min(&_input[j]);
+                let area = (size.x + 1) * (size.y + 1);
                 if area > largest_area {
                     largest_area = area;
                 }
@@ -39,7 +41,7 @@ pub fn part1(_input: &ParsedInput) -> u64 {
 
 pub fn part2(_input: &ParsedInput) -> u64 {
     // Build list of polygon edges (each edge is a line segment)
-    let edges: Vec<((u64, u64), (u64, u64))> = (0.._input.len())
+    let edges: Vec<Edge> = (0.._input.len())
         .map(|i| {
             let next = (i + 1) % _input.len();
             (_input[i], _input[next])
@@ -49,14 +51,14 @@ pub fn part2(_input: &ParsedInput) -> u64 {
     // Pre-compute and sort vertical and horizontal edges for faster lookup
     let vertical_edges: Vec<(u64, u64, u64)> = edges
         .iter()
-        .filter(|&&((ex1, _), (ex2, _))| ex1 == ex2)
-        .map(|&((ex1, ey1), (_, ey2))| (ex1, ey1.min(ey2), ey1.max(ey2)))
+        .filter(|&&(a, b)| a.x == b.x)
+        .map(|&(a, b)| (a.x, a.y.min(b.y), a.y.max(b.y)))
         .collect();
 
     let horizontal_edges: Vec<(u64, u64, u64)> = edges
         .iter()
-        .filter(|&&((_, ey1), (_, ey2))| ey1 == ey2)
-        .map(|&((ex1, ey1), (ex2, _))| (ey1, ex1.min(ex2), ex1.max(ex2)))
+        .filter(|&&(a, b)| a.y == b.y)
+        .map(|&(a, b)| (a.y, a.x.min(b.x), a.x.max(b.x)))
         .collect();
 
     // Generate all pairs of indices
@@ -69,25 +71,18 @@ pub fn part2(_input: &ParsedInput) -> u64 {
     pairs
         .par_iter()
         .filter_map(|&(i, j)| {
-            let (x1, y1) = _input[i];
-            let (x2, y2) = _input[j];
-
-            let min_x = x1.min(x2);
-            let max_x = x1.max(x2);
-            let min_y = y1.min(y2);
-            let max_y = y1.max(y2);
+            let min = _input[i].min(&_input[j]);
+            let max = _input[i].max(&_input[j]);
 
             if is_rectangle_inside_polygon_fast(
                 &vertical_edges,
                 &horizontal_edges,
                 &edges,
                 _input,
-                min_x,
-                max_x,
-                min_y,
-                max_y,
+                min,
+                max,
             ) {
-                Some((max_x - min_x + 1) * (max_y - min_y + 1))
+                Some((max.x - min.x + 1) * (max.y - min.y + 1))
             } else {
                 None
             }
@@ -100,36 +95,31 @@ pub fn part2(_input: &ParsedInput) -> u64 {
 fn is_rectangle_inside_polygon_fast(
     vertical_edges: &[(u64, u64, u64)],
     horizontal_edges: &[(u64, u64, u64)],
-    edges: &[((u64, u64), (u64, u64))],
-    polygon: &[(u64, u64)],
-    min_x: u64,
-    max_x: u64,
-    min_y: u64,
-    max_y: u64,
+    edges: &[Edge],
+    polygon: &[Point<u64>],
+    min: Point<u64>,
+    max: Point<u64>,
 ) -> bool {
+    let (min_x, min_y, max_x, max_y) = (min.x, min.y, max.x, max.y);
+
     // First check: the center of the rectangle must be inside the polygon
-    let center_x = (min_x + max_x) / 2;
-    let center_y = (min_y + max_y) / 2;
+    let center = Point::new((min_x + max_x) / 2, (min_y + max_y) / 2);
 
-    if !is_point_inside_or_on_polygon(polygon, edges, center_x, center_y) {
+    if !is_point_inside_or_on_polygon(polygon, edges, center) {
         return false;
     }
 
     // Check vertical edges that cross interior
     for &(edge_x, edge_min_y, edge_max_y) in vertical_edges {
-        if edge_x > min_x && edge_x < max_x {
-            if edge_min_y < max_y && edge_max_y > min_y {
-                return false;
-            }
+        if edge_x > min_x && edge_x < max_x && edge_min_y < max_y && edge_max_y > min_y {
+            return false;
         }
     }
 
     // Check horizontal edges that cross interior
     for &(edge_y, edge_min_x, edge_max_x) in horizontal_edges {
-        if edge_y > min_y && edge_y < max_y {
-            if edge_min_x < max_x && edge_max_x > min_x {
-                return false;
-            }
+        if edge_y > min_y && edge_y < max_y && edge_min_x < max_x && edge_max_x > min_x {
+            return false;
         }
     }
 
@@ -137,17 +127,15 @@ fn is_rectangle_inside_polygon_fast(
 }
 
 fn is_point_inside_or_on_polygon(
-    polygon: &[(u64, u64)],
-    edges: &[((u64, u64), (u64, u64))],
-    x: u64,
-    y: u64,
+    polygon: &[Point<u64>],
+    edges: &[Edge],
+    point: Point<u64>,
 ) -> bool {
     // Check if point is on boundary
-    for &((ex1, ey1), (ex2, ey2)) in edges {
-        if point_on_segment(x, y, ex1, ey1, ex2, ey2) {
-            return true;
-        }
+    if edges.iter().any(|&edge| point_on_segment(point, edge)) {
+        return true;
     }
+    let Point { x, y } = point;
 
     // Ray casting for interior
     let mut inside = false;
@@ -155,8 +143,8 @@ fn is_point_inside_or_on_polygon(
 
     for i in 0..n {
         let j = (i + 1) % n;
-        let (xi, yi) = polygon[i];
-        let (xj, yj) = polygon[j];
+        let Point { x: xi, y: yi } = polygon[i];
+        let Point { x: xj, y: yj } = polygon[j];
 
         let intersect = ((yi > y) != (yj > y))
             && ((x as i64)
@@ -171,15 +159,10 @@ fn is_point_inside_or_on_polygon(
     inside
 }
 
-fn point_on_segment(px: u64, py: u64, x1: u64, y1: u64, x2: u64, y2: u64) -> bool {
-    // For axis-aligned segments only
-    if x1 == x2 {
-        // Vertical segment
-        px == x1 && py >= y1.min(y2) && py <= y1.max(y2)
-    } else if y1 == y2 {
-        // Horizontal segment
-        py == y1 && px >= x1.min(x2) && px <= x1.max(x2)
-    } else {
-        false
-    }
+fn point_on_segment(point: Point<u64>, (a, b): Edge) -> bool {
+    // For axis-aligned segments only, which are their own bounding box
+    let (min, max) = (a.min(&b), a.max(&b));
+    (a.x == b.x || a.y == b.y)
+        && (min.x..=max.x).contains(&point.x)
+        && (min.y..=max.y).contains(&point.y)
 }
@@ -1,97 +1,98 @@
-type ParsedInput = Vec<(String, Vec<String>)>;
+use crate::utils::graph::PathCountError;
+use crate::utils::parse::{trimmed_lines, ParseError};
+use crate::utils::Graph;
+use std::collections::HashMap;
 
-pub fn parse(input: &str) -> ParsedInput {
+pub type ParsedInput = Graph;
+
+// Named devices
+const YOU: &str = "you";
+const SERVER: &str = "svr";
+const DAC: &str = "dac";
+const FFT: &str = "fft";
+const OUT: &str = "out";
+
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
     // Each line is structured as "key: val val val"
-    // We parse it into a vector of (key, [vals])
-    input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split(':');
-            let key = parts.next().unwrap().trim().to_string();
-            let vals = parts
-                .next()
-                .unwrap_or("")
-                .trim()
-                .split_whitespace()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            (key, vals)
-        })
-        .collect()
-}
+    // Every value is an edge from the key to that device
+    let mut graph = Graph::new();
+    let mut lines = HashMap::new();
+    for line in trimmed_lines(input) {
+        let (key, vals) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::at(input, line, "expected `name: outputs`"))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(ParseError::at(input, line, "missing device name"));
+        }
+        let device = graph.intern(key);
+        lines.insert(device, line);
+        for output in vals.split_whitespace() {
+            let output = graph.intern(output);
+            graph.add_edge(device, output);
+        }
+    }
 
-pub fn part1(_input: &ParsedInput) -> u32 {
-    // The strings represent machines (or nodes) and their connections.
-    // We have two named nodes, "you" and "out".
-    // In part 1, we need to find the total number of paths from "you" to "out".
-    // We'll use BFS (breadth-first search) to explore all paths.
-    use std::collections::{HashMap, VecDeque};
-    let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
-    for (key, vals) in _input.iter() {
-        graph.insert(key.as_str(), vals.iter().map(|s| s.as_str()).collect());
+    // Paths end at "out", so nothing can lead on from it
+    if let Some(out) = graph.id(OUT).filter(|&out| !graph.neighbors(out).is_empty()) {
+        return Err(ParseError::at(input, lines[&out], "\"out\" can't have outputs"));
     }
-    let mut path_count: u32 = 0;
-    let mut queue: VecDeque<&str> = VecDeque::new();
-    queue.push_back("you");
-    while let Some(current) = queue.pop_front() {
-        if current == "out" {
-            path_count += 1;
+
+    // A cycle on the way to "out" would make the number of paths infinite, so check
+    // the counts both parts rely on. Part 2's paths are some of those from "svr".
+    // Every device on a cycle has outputs, so it has a line to point at, and so
+    // does the start of anything with too many paths.
+    let mut paths = graph.path_counter();
+    for start in [YOU, SERVER] {
+        let (Some(from), Some(out)) = (graph.id(start), graph.id(OUT)) else {
             continue;
-        }
-        if let Some(neighbors) = graph.get(current) {
-            for &neighbor in neighbors {
-                queue.push_back(neighbor);
+        };
+        match paths.count(from, out) {
+            Ok(_) => {}
+            Err(PathCountError::Cycle(cycle)) => {
+                return Err(ParseError::at(input, lines[&cycle[0]], "devices form a cycle"));
+            }
+            Err(PathCountError::Overflow) => {
+                return Err(ParseError::at(input, lines[&from], "too many paths to count"));
             }
         }
     }
-    path_count
+    Ok(graph)
+}
+
+pub fn part1(_input: &ParsedInput) -> u64 {
+    // The strings represent machines (or nodes) and their connections.
+    // In part 1, we need to find the total number of paths from "you" to "out".
+    let (Some(you), Some(out)) = (_input.id(YOU), _input.id(OUT)) else {
+        return 0;
+    };
+    let mut paths = _input.path_counter();
+    paths.count(you, out).expect("parse checks the paths from you")
 }
 
 pub fn part2(_input: &ParsedInput) -> u64 {
     // In part 2, we need to find the paths from "svr" to "out"
     // However, we should only count paths that pass through both "dac" AND "fft"
-    // Use memoization: count paths from (node, seen_dac, seen_fft) to "out"
-    // Attempting to explore all paths is veeeeeery slow without memoization
-    use std::collections::HashMap;
-
-    let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
-    for (key, vals) in _input.iter() {
-        graph.insert(key.as_str(), vals.iter().map(|s| s.as_str()).collect());
-    }
-
-    // Memoization cache: (node, seen_dac, seen_fft) -> path count
-    let mut memo: HashMap<(&str, bool, bool), u64> = HashMap::new();
-
-    fn count_paths<'a>(
-        node: &'a str,
-        seen_dac: bool,
-        seen_fft: bool,
-        graph: &HashMap<&'a str, Vec<&'a str>>,
-        memo: &mut HashMap<(&'a str, bool, bool), u64>,
-    ) -> u64 {
-        // Update flags for current node
-        let seen_dac = seen_dac || node == "dac";
-        let seen_fft = seen_fft || node == "fft";
-
-        if node == "out" {
-            return if seen_dac && seen_fft { 1 } else { 0 };
-        }
-
-        let key = (node, seen_dac, seen_fft);
-        if let Some(&cached) = memo.get(&key) {
-            return cached;
+    // Each order's count is the product of the path counts between consecutive stops.
+    // When every stop leads to the next, all those paths are paths from "svr" to "out",
+    // which parse checked are finite and few enough to count.
+    let ids = [SERVER, DAC, FFT, OUT].map(|name| _input.id(name));
+    let [Some(server), Some(dac), Some(fft), Some(out)] = ids else {
+        return 0;
+    };
+    let mut paths = _input.path_counter();
+    let mut via = |stops: [usize; 4]| {
+        if !stops.windows(2).all(|pair| _input.reaches(pair[0], pair[1])) {
+            return 0;
         }
-
-        let mut total = 0u64;
-        if let Some(neighbors) = graph.get(node) {
-            for &neighbor in neighbors {
-                total += count_paths(neighbor, seen_dac, seen_fft, graph, memo);
-            }
-        }
-
-        memo.insert(key, total);
-        total
-    }
-
-    count_paths("svr", false, false, &graph, &mut memo)
+        stops.windows(2).fold(1u64, |product, pair| {
+            let count = paths.count(pair[0], pair[1]);
+            count
+                .ok()
+                .and_then(|count| product.checked_mul(count))
+                .expect("parse checks the paths from svr")
+        })
+    };
+    let total = via([server, dac, fft, out]).checked_add(via([server, fft, dac, out]));
+    total.expect("parse checks the paths from svr")
 }
@@ -1,8 +1,10 @@
-type ParsedInput = Vec<String>;
+use crate::utils::ParseError;
 
-pub fn parse(input: &str) -> ParsedInput {
+pub type ParsedInput = Vec<String>;
+
+pub fn parse(input: &str) -> Result<ParsedInput, ParseError> {
     // TODO: Implement parsing
-    input.lines().map(|s| s.to_string()).collect()
+    Ok(input.lines().map(|s| s.to_string()).collect())
 }
 
 pub fn part1(_input: &ParsedInput) -> &'static str {
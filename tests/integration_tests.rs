@@ -1 +1,3 @@
+mod registry;
+mod runner;
 mod year2025;
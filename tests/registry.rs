@@ -0,0 +1,58 @@
+use aoc_25::answers::{Ledger, Status};
+use aoc_25::puzzle::{Puzzle, Visitor};
+use aoc_25::utils::{resolve_input, InputConfig};
+use std::collections::BTreeMap;
+
+/// Runs every registered puzzle against its example input and checks the answers
+/// against the example ledger (`answers/yearXXXX_test.toml`), skipping parts
+/// with no recorded answer
+#[derive(Default)]
+struct Examples {
+    visited: Vec<(u32, u32)>,
+    ledgers: BTreeMap<u32, Ledger>,
+}
+
+impl Visitor for Examples {
+    fn visit<P: Puzzle>(&mut self) {
+        let config = InputConfig {
+            use_test: true,
+            ..InputConfig::default()
+        };
+        let path = resolve_input(P::YEAR, P::DAY, &config);
+        let data = path
+            .read()
+            .unwrap_or_else(|_| panic!("Missing example {path}"));
+
+        let input = P::parse(&data).unwrap_or_else(|e| panic!("Invalid example {path}: {e}"));
+        let answers = [P::part1(&input).to_string(), P::part2(&input).to_string()];
+
+        let ledger = self.ledgers.entry(P::YEAR).or_insert_with(|| {
+            let ledger_path = Ledger::path_for(P::YEAR, true);
+            Ledger::load(&ledger_path)
+                .unwrap_or_else(|e| panic!("Unreadable {}: {e}", ledger_path.display()))
+        });
+        for (part, answer) in (1..=2).zip(&answers) {
+            match ledger.check(P::DAY, part, answer) {
+                Status::Pass => {}
+                // Nothing recorded yet, e.g. a day that isn't solved
+                Status::Unknown => {}
+                status => panic!(
+                    "{} day {:02} part {part} gave {answer}: {status}",
+                    P::YEAR,
+                    P::DAY
+                ),
+            }
+        }
+
+        self.visited.push((P::YEAR, P::DAY));
+    }
+}
+
+#[test]
+fn registry_test() {
+    let mut examples = Examples::default();
+    aoc_25::visit_puzzles(&mut examples);
+
+    assert!(!examples.visited.is_empty());
+    assert!(examples.visited.windows(2).all(|w| w[0] < w[1]));
+}
@@ -0,0 +1,33 @@
+use aoc_25::runner::{self, SolveError};
+
+const DAY01: &str = include_str!("year2025/day01_input.txt");
+
+#[test]
+fn solve_test() {
+    let answers = runner::solve(2025, 1, DAY01).unwrap();
+    assert_eq!(answers.part1, "3");
+    assert_eq!(answers.part2, "6");
+}
+
+#[test]
+fn solve_error_test() {
+    assert_eq!(
+        runner::solve(2025, 26, DAY01),
+        Err(SolveError::UnknownPuzzle {
+            year: 2025,
+            day: 26
+        })
+    );
+    assert!(matches!(
+        runner::solve(2025, 1, "X12"),
+        Err(SolveError::Parse(_))
+    ));
+}
+
+#[test]
+fn puzzles_test() {
+    let puzzles: Vec<(u32, u32)> = runner::puzzles().map(|s| (s.year, s.day)).collect();
+    assert!(puzzles.contains(&(2025, 1)));
+    assert!(puzzles.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(runner::find(2025, 1).map(|s| s.day), Some(1));
+}
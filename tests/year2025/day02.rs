@@ -4,12 +4,28 @@ const INPUT: &str = include_str!("day02_input.txt");
 
 #[test]
 fn part1_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part1(&parsed), 1227775554);
 }
 
 #[test]
 fn part2_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part2(&parsed), 4174379265);
 }
+
+#[test]
+fn overlapping_ranges_test() {
+    // Each range counts its own invalid IDs, even where they overlap
+    let parsed = parse("11-22,11-22").unwrap();
+    assert_eq!(part1(&parsed), 2 * (11 + 22));
+    assert_eq!(part2(&parsed), 2 * (11 + 22));
+}
+
+#[test]
+fn totals_beyond_u64_test() {
+    // The 20-digit invalid IDs alone add up to far more than a u64 holds
+    let parsed = parse("1-18446744073709551615").unwrap();
+    assert!(part1(&parsed) > u64::MAX as u128);
+    assert!(part2(&parsed) > part1(&parsed));
+}
@@ -4,12 +4,30 @@ const INPUT: &str = include_str!("day05_input.txt");
 
 #[test]
 fn part1_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part1(&parsed), 3);
 }
 
 #[test]
 fn part2_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part2(&parsed), 14);
 }
+
+#[test]
+fn parse_error_test() {
+    let error = parse("3-5\n10-14\n1\n5").unwrap_err();
+    assert_eq!((error.line, error.column), (4, 2));
+}
+
+#[test]
+fn ranges_up_to_max_test() {
+    let parsed =
+        parse("18446744073709551614-18446744073709551615\n1-2\n2-3\n\n18446744073709551615\n0")
+            .unwrap();
+    assert_eq!(part1(&parsed), 1);
+    assert_eq!(part2(&parsed), 5);
+
+    let parsed = parse("0-18446744073709551615\n\n7").unwrap();
+    assert_eq!(part2(&parsed), 1 << 64);
+}
@@ -4,12 +4,18 @@ const INPUT: &str = include_str!("day08_input.txt");
 
 #[test]
 fn part1_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part1(&parsed), 40);
 }
 
+#[test]
+fn largest_circuits_test() {
+    let parsed = parse(INPUT).unwrap();
+    assert_eq!(largest_circuits(&parsed, 10), 40);
+}
+
 #[test]
 fn part2_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part2(&parsed), 25272);
 }
@@ -4,12 +4,18 @@ const INPUT: &str = include_str!("day09_input.txt");
 
 #[test]
 fn part1_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part1(&parsed), 50);
 }
 
 #[test]
 fn part2_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part2(&parsed), 24);
 }
+
+#[test]
+fn parse_error_test() {
+    let error = parse("7,1\n11;1\n").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+}
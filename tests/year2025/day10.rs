@@ -4,12 +4,31 @@ const INPUT: &str = include_str!("day10_input.txt");
 
 #[test]
 fn part1_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part1(&parsed), 7);
 }
 
 #[test]
 fn part2_test() {
-    let parsed = parse(INPUT);
-    assert_eq!(part2(&parsed), 33);
+    let parsed = parse(INPUT).unwrap();
+    assert_eq!(part2(&parsed), Presses::Total(33));
+}
+
+#[test]
+fn parse_error_test() {
+    let error = parse("[.##.] (3) (1,x) {3,5,4,7}").unwrap_err();
+    assert_eq!((error.line, error.column), (1, 15));
+}
+
+#[test]
+fn unreachable_joltage_test() {
+    let error = parse("[.#] (1) {0,1}\n[##] (0,1) {1,2}").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+}
+
+#[test]
+fn negative_presses_test() {
+    // Consistent, but only with -1 presses of the first button
+    let parsed = parse("[..] (0) (0,1) {0,1}").unwrap();
+    assert_eq!(part2(&parsed), Presses::Unreachable(1));
 }
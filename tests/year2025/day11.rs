@@ -5,12 +5,31 @@ const INPUT2: &str = include_str!("day11_input2.txt");
 
 #[test]
 fn part1_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part1(&parsed), 5);
 }
 
 #[test]
 fn part2_test() {
-    let parsed = parse(INPUT2);
+    let parsed = parse(INPUT2).unwrap();
     assert_eq!(part2(&parsed), 2);
 }
+
+#[test]
+fn parse_error_test() {
+    let error = parse("you: a\na: b out\nb: a\n").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+}
+
+#[test]
+fn unrelated_cycle_test() {
+    // Devices that "you" never reaches can loop without making the count infinite
+    let parsed = parse("you: a out\na: out\nx: y\ny: x\n").unwrap();
+    assert_eq!(part1(&parsed), 2);
+}
+
+#[test]
+fn out_with_outputs_test() {
+    let error = parse("you: out\nout: you\n").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+}
@@ -5,13 +5,13 @@ const INPUT: &str = include_str!("day12_input.txt");
 #[test]
 #[ignore] // Remove when you have expected values
 fn part1_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part1(&parsed), "expected_result");
 }
 
 #[test]
 #[ignore] // Remove when you have expected values
 fn part2_test() {
-    let parsed = parse(INPUT);
+    let parsed = parse(INPUT).unwrap();
     assert_eq!(part2(&parsed), "expected_result");
 }